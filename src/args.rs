@@ -0,0 +1,222 @@
+use std::env;
+use std::process;
+use std::str::FromStr;
+
+use lazy_static::lazy_static;
+
+use crate::config::Configuration;
+
+const USAGE: &str = "Usage: disasterserver_new [options]
+
+Options:
+    --config <path>          Config file (default: Config.toml)
+    --log-dir <path>         Directory for log files (default: logs)
+    --tcp-port <port>        Override server.tcp_port
+    --udp-port <port>        Override server.udp_port
+    --grow, --no-grow        Override server.grow
+    --grow-limit <count>     Override server.grow_limit
+    --debug, --no-debug      Override debug
+    --print-config           Print effective configuration and exit
+    --migrate-config         Rewrite config file with all keys and comments
+    --allow-invalid-config   Use defaults instead of exiting on invalid config
+    --help                   Show this message
+
+Environment variables (command line takes precedence):
+    BETTERSERVER_CONFIG, BETTERSERVER_LOG_DIR, BETTERSERVER_TCP_PORT, BETTERSERVER_UDP_PORT,
+    BETTERSERVER_GROW, BETTERSERVER_GROW_LIMIT, BETTERSERVER_DEBUG";
+
+lazy_static! {
+    pub(crate) static ref ARGS: Arguments = parse();
+}
+
+// Applied on top of the config file, every time it's loaded
+#[derive(Default)]
+pub(crate) struct Overrides
+{
+    pub tcp_port: Option<u16>,
+    pub udp_port: Option<u16>,
+    pub grow: Option<bool>,
+    pub grow_limit: Option<u16>,
+    pub debug: Option<bool>
+}
+
+pub(crate) struct Arguments
+{
+    pub config_path: String,
+    pub log_dir: String,
+    pub print_config: bool,
+    pub migrate_config: bool,
+    pub allow_invalid_config: bool,
+    pub overrides: Overrides
+}
+
+impl Overrides
+{
+    pub fn apply(&self, config: &mut Configuration)
+    {
+        if let Some(value) = self.tcp_port {
+            config.server.tcp_port = value;
+        }
+
+        if let Some(value) = self.udp_port {
+            config.server.udp_port = value;
+        }
+
+        if let Some(value) = self.grow {
+            config.server.grow = value;
+        }
+
+        if let Some(value) = self.grow_limit {
+            config.server.grow_limit = value;
+        }
+
+        if let Some(value) = self.debug {
+            config.debug = value;
+        }
+    }
+}
+
+fn fail(message: &str) -> !
+{
+    eprintln!("{}\n\n{}", message, USAGE);
+    process::exit(2);
+}
+
+fn value<T: FromStr>(name: &str, value: Option<String>) -> Result<T, String>
+{
+    match value {
+        Some(value) => value.parse().map_err(|_| format!("Invalid value \"{}\" for {}", value, name)),
+        None => Err(format!("Missing value for {}", name))
+    }
+}
+
+fn env_value<T: FromStr>(env: &dyn Fn(&str) -> Option<String>, name: &str) -> Result<Option<T>, String>
+{
+    match env(name) {
+        Some(value) => self::value(name, Some(value)).map(Some),
+        None => Ok(None)
+    }
+}
+
+fn env_bool(env: &dyn Fn(&str) -> Option<String>, name: &str) -> Result<Option<bool>, String>
+{
+    let value = match env(name) {
+        Some(res) => res,
+        None => return Ok(None)
+    };
+
+    match value.to_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Ok(Some(true)),
+        "0" | "false" | "no" | "off" => Ok(Some(false)),
+        _ => Err(format!("Invalid value \"{}\" for {}", value, name))
+    }
+}
+
+fn parse() -> Arguments
+{
+    match parse_from(&|x| env::var(x).ok(), env::args().skip(1)) {
+        Ok(res) => res,
+        Err(err) => fail(&err)
+    }
+}
+
+fn parse_from(env: &dyn Fn(&str) -> Option<String>, mut iter: impl Iterator<Item = String>) -> Result<Arguments, String>
+{
+    // Environment first, command line overwrites it
+    let mut args = Arguments {
+        config_path: env("BETTERSERVER_CONFIG").unwrap_or("Config.toml".to_string()),
+        log_dir: env("BETTERSERVER_LOG_DIR").unwrap_or("logs".to_string()),
+        print_config: false,
+        migrate_config: false,
+        allow_invalid_config: false,
+        overrides: Overrides {
+            tcp_port: env_value(env, "BETTERSERVER_TCP_PORT")?,
+            udp_port: env_value(env, "BETTERSERVER_UDP_PORT")?,
+            grow: env_bool(env, "BETTERSERVER_GROW")?,
+            grow_limit: env_value(env, "BETTERSERVER_GROW_LIMIT")?,
+            debug: env_bool(env, "BETTERSERVER_DEBUG")?
+        }
+    };
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--config" => args.config_path = value(&arg, iter.next())?,
+            "--log-dir" => args.log_dir = value(&arg, iter.next())?,
+            "--tcp-port" => args.overrides.tcp_port = Some(value(&arg, iter.next())?),
+            "--udp-port" => args.overrides.udp_port = Some(value(&arg, iter.next())?),
+            "--grow" => args.overrides.grow = Some(true),
+            "--no-grow" => args.overrides.grow = Some(false),
+            "--grow-limit" => args.overrides.grow_limit = Some(value(&arg, iter.next())?),
+            "--debug" => args.overrides.debug = Some(true),
+            "--no-debug" => args.overrides.debug = Some(false),
+            "--print-config" => args.print_config = true,
+            "--migrate-config" => args.migrate_config = true,
+            "--allow-invalid-config" => args.allow_invalid_config = true,
+
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            },
+
+            _ => return Err(format!("Unknown argument \"{}\"", arg))
+        }
+    }
+
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn parse_with(env: &[(&str, &str)], args: &[&str]) -> Result<Arguments, String>
+    {
+        let env: HashMap<String, String> = env.iter().map(|x| (x.0.to_string(), x.1.to_string())).collect();
+        parse_from(&|x| env.get(x).cloned(), args.iter().map(|x| x.to_string()))
+    }
+
+    #[test]
+    fn command_line_beats_environment_beats_file()
+    {
+        let mut config: Configuration = toml::from_str("[server]\ntcp_port = 1000\nudp_port = 2000\ngrow = false").unwrap();
+        let args = parse_with(&[("BETTERSERVER_TCP_PORT", "3000"), ("BETTERSERVER_UDP_PORT", "4000")], &["--tcp-port", "5000", "--grow"]).unwrap();
+        args.overrides.apply(&mut config);
+
+        assert_eq!(config.server.tcp_port, 5000);
+        assert_eq!(config.server.udp_port, 4000);
+        assert!(config.server.grow);
+    }
+
+    #[test]
+    fn file_is_kept_without_overrides()
+    {
+        let mut config: Configuration = toml::from_str("debug = false\n[server]\ntcp_port = 1000").unwrap();
+        let args = parse_with(&[], &[]).unwrap();
+        args.overrides.apply(&mut config);
+
+        assert_eq!(config.server.tcp_port, 1000);
+        assert!(!config.debug);
+        assert_eq!(args.config_path, "Config.toml");
+    }
+
+    #[test]
+    fn environment_paths()
+    {
+        let args = parse_with(&[("BETTERSERVER_CONFIG", "a.toml"), ("BETTERSERVER_LOG_DIR", "env")], &["--log-dir", "cli"]).unwrap();
+        assert_eq!(args.config_path, "a.toml");
+        assert_eq!(args.log_dir, "cli");
+    }
+
+    #[test]
+    fn invalid_values()
+    {
+        assert!(parse_with(&[], &["--tcp-port"]).is_err());
+        assert!(parse_with(&[], &["--tcp-port", "70000"]).is_err());
+        assert!(parse_with(&[], &["--unknown"]).is_err());
+        assert!(parse_with(&[("BETTERSERVER_DEBUG", "maybe")], &[]).is_err());
+        assert!(parse_with(&[("BETTERSERVER_GROW_LIMIT", "x")], &[]).is_err());
+    }
+}
@@ -0,0 +1,368 @@
+use std::cmp::Reverse;
+use std::collections::{HashSet, HashMap};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+use log::{info, warn};
+use rand::{thread_rng, Rng};
+
+use crate::{config, server::{Server, Peer, real_peers}, packet::{Packet, PacketType}};
+
+const CODE_CHARS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+lazy_static! {
+    pub(crate) static ref SERVERS: RwLock<Vec<SubServer>> = RwLock::new(Vec::new());
+    static ref TRANSFERS: Mutex<Vec<Transfer>> = Mutex::new(Vec::new());
+}
+
+// Things we need to know without locking the server
+#[derive(Clone)]
+pub(crate) struct SubServer
+{
+    pub port: u16,
+    pub code: Option<String>, // Set for private rooms
+    pub server: Arc<Mutex<Server>>
+}
+
+// Peer that asked to be moved out of a sub-server
+struct Transfer
+{
+    server: String,
+    code: Option<String>,
+    peer: Arc<Mutex<Peer>>
+}
+
+pub(crate) fn start()
+{
+    let _ = thread::Builder::new().name("balancer".to_string()).spawn(move || {
+        worker();
+    });
+}
+
+pub(crate) fn allocate(code: Option<String>) -> Option<Arc<Mutex<Server>>>
+{
+    let config = config::get();
+    let taken: Vec<u16> = SERVERS.read().unwrap().iter().map(|x| x.port).collect();
+    if taken.len() >= config.server.grow_limit as usize {
+        warn!("Couldn't allocate new server: FULL ({}/{})!", taken.len(), config.server.grow_limit);
+        return None;
+    }
+
+    info!("Allocating new sub-server... ({}/{})", taken.len() + 1, config.server.grow_limit);
+
+    // Bind before taking the list lock. Closed rooms keep their socket until
+    // their UDP thread exits, so a port is only reused once it's really free
+    let first = config.server.udp_port;
+    let mut server = None;
+    for port in (first..=first.saturating_add(config.server.grow_limit - 1)).filter(|x| !taken.contains(x)) {
+        match Server::start(port, format!("server{}", port - first))
+        {
+            Ok(res) => {
+                server = Some((port, res));
+                break;
+            },
+            Err(err) => warn!("Couldn't bind {}/udp: {}", port, err)
+        }
+    }
+
+    let (port, server) = match server {
+        Some(res) => res,
+        None => {
+            warn!("Couldn't allocate new server: no free ports!");
+            return None;
+        }
+    };
+
+    // Someone else could allocate while we were binding
+    let mut servers = SERVERS.write().unwrap();
+    if servers.len() >= config.server.grow_limit as usize || servers.iter().any(|x| x.port == port) {
+        warn!("Couldn't allocate new server: FULL ({}/{})!", servers.len(), config.server.grow_limit);
+        server.lock().unwrap().stop();
+        return None;
+    }
+
+    servers.push(SubServer { port, code, server: server.clone() });
+    Some(server)
+}
+
+pub(crate) fn find_free_server() -> Arc<Mutex<Server>>
+{
+    let servers: Vec<SubServer> = SERVERS.read().unwrap().iter().filter(|x| x.code.is_none()).cloned().collect();
+    if !config::get().server.grow {
+        return servers.first().unwrap().server.clone();
+    }
+
+    for sub in servers.iter() {
+        if !is_full(&sub.server.lock().unwrap()) {
+            return sub.server.clone();
+        }
+    }
+
+    match allocate(None) {
+        Some(server) => server,
+        None => servers.last().unwrap().server.clone()
+    }
+}
+
+// Creates a private room and returns it's join code
+pub(crate) fn create_room() -> Option<String>
+{
+    let code = loop {
+        let code: String = (0..5).map(|_| CODE_CHARS[thread_rng().gen_range(0..CODE_CHARS.len())] as char).collect();
+
+        if !SERVERS.read().unwrap().iter().any(|x| x.code.as_ref() == Some(&code)) {
+            break code;
+        }
+    };
+
+    allocate(Some(code.clone()))?;
+    info!("Created private room [{}]", code);
+    Some(code)
+}
+
+pub(crate) fn room_exists(code: &str) -> bool
+{
+    let code = code.to_uppercase();
+    SERVERS.read().unwrap().iter().any(|x| x.code.as_ref() == Some(&code))
+}
+
+// Called from the peer's current sub-server, actual move happens on balancer's thread
+pub(crate) fn request_move(server: &Server, peer: Arc<Mutex<Peer>>)
+{
+    TRANSFERS.lock().unwrap().push(Transfer { server: server.name.clone(), code: None, peer });
+}
+
+pub(crate) fn request_join(server: &Server, peer: Arc<Mutex<Peer>>, code: &str)
+{
+    TRANSFERS.lock().unwrap().push(Transfer { server: server.name.clone(), code: Some(code.to_uppercase()), peer });
+}
+
+// New bans apply to players that are already connected
+pub(crate) fn kick_banned()
+{
+    let bans = config::get().bans.clone();
+    let servers = SERVERS.read().unwrap().clone();
+
+    for sub in servers {
+        let server = sub.server.lock().unwrap();
+        for peer in server.peers.read().unwrap().values() {
+            let mut peer = peer.lock().unwrap();
+            if !peer.udid.is_empty() && bans.contains(&peer.udid) {
+                peer.disconnect("You are banned.");
+            }
+        }
+    }
+}
+
+fn worker()
+{
+    let mut offered: HashSet<(String, u16)> = HashSet::new();
+    let mut empty_rooms: HashMap<u16, u16> = HashMap::new();
+    let mut merge_timer: u16 = 0;
+
+    loop {
+        thread::sleep(Duration::from_millis(500));
+
+        // Never hold the list lock while locking servers
+        let servers = SERVERS.read().unwrap().clone();
+        let transfers: Vec<Transfer> = TRANSFERS.lock().unwrap().drain(..).collect();
+
+        for transfer in transfers {
+            do_transfer(&servers, transfer);
+        }
+
+        offer_moves(&servers, &mut offered);
+        close_rooms(&servers, &mut empty_rooms);
+
+        merge_timer += 1;
+        if merge_timer >= 30 {
+            merge_lobbies(&servers);
+            merge_timer = 0;
+        }
+    }
+}
+
+fn do_transfer(servers: &[SubServer], transfer: Transfer)
+{
+    let source = match servers.iter().find(|x| x.server.lock().unwrap().name == transfer.server) {
+        Some(res) => res,
+        None => return
+    };
+
+    // Peer left in the meantime
+    let id = transfer.peer.lock().unwrap().id();
+    if !source.server.lock().unwrap().peers.read().unwrap().contains_key(&id) {
+        return;
+    }
+
+    let target = match &transfer.code {
+        Some(code) => servers.iter().find(|x| x.code.as_ref() == Some(code)),
+        None => servers.iter().find(|x| !Arc::ptr_eq(&x.server, &source.server) && is_free_lobby(x))
+    };
+
+    let target = match target {
+        Some(res) if !Arc::ptr_eq(&res.server, &source.server) => res,
+        Some(_) => {
+            send_message(&mut transfer.peer.lock().unwrap(), "you are already there");
+            return;
+        },
+        None => {
+            match transfer.code {
+                Some(_) => send_message(&mut transfer.peer.lock().unwrap(), "no room with this code"),
+                None => send_message(&mut transfer.peer.lock().unwrap(), "no free lobby right now, try again later")
+            }
+            return;
+        }
+    };
+
+    let mut target_guard = target.server.lock().unwrap();
+    if !is_open_lobby(&target_guard) {
+        send_message(&mut transfer.peer.lock().unwrap(), "can't join right now, try again later");
+        return;
+    }
+
+    Server::peer_transfer(&mut source.server.lock().unwrap(), &mut target_guard, target.server.clone(), transfer.peer);
+}
+
+// Let queued players know that they don't have to wait for the round to end
+fn offer_moves(servers: &[SubServer], offered: &mut HashSet<(String, u16)>)
+{
+    let mut queued = HashSet::new();
+    let has_free = servers.iter().any(is_free_lobby);
+
+    for sub in servers.iter() {
+        let server = sub.server.lock().unwrap();
+        if server.state.lock().unwrap().name() != "Game" {
+            continue;
+        }
+
+        for peer in server.peers.read().unwrap().values() {
+            let mut peer = peer.lock().unwrap();
+            if !peer.in_queue || peer.pending {
+                continue;
+            }
+
+            let key = (server.name.clone(), peer.id());
+            if has_free && !offered.contains(&key) {
+                send_message(&mut peer, "another lobby has free space, type .move to join it");
+            }
+
+            queued.insert(key);
+        }
+    }
+
+    if has_free {
+        *offered = queued;
+    }
+    else {
+        offered.retain(|x| queued.contains(x));
+    }
+}
+
+// Lobbies with one or two players can't really start, so gather them in one place
+fn merge_lobbies(servers: &[SubServer])
+{
+    let mut sparse = Vec::new();
+    for sub in servers.iter() {
+        if sub.code.is_some() {
+            continue;
+        }
+
+        let guard = sub.server.lock().unwrap();
+        if guard.state.lock().unwrap().name() != "Lobby" {
+            continue;
+        }
+
+        // Someone is still connecting
+        if guard.peers.read().unwrap().values().any(|x| x.lock().unwrap().pending) {
+            continue;
+        }
+
+        let count = real_peers!(guard).count();
+        let ready = real_peers!(guard).filter(|x| x.lock().unwrap().ready).count();
+        if count == 0 || count > 2 || (count > 1 && ready == count) {
+            continue;
+        }
+
+        sparse.push((&sub.server, count));
+    }
+
+    if sparse.len() < 2 {
+        return;
+    }
+
+    // Most populated lobby keeps its players
+    sparse.sort_by_key(|x| Reverse(x.1));
+    let (target, mut count) = sparse[0];
+    let max_players = config::get().rules(&target.lock().unwrap().name, None).max_players as usize;
+
+    for (source, size) in sparse.iter().skip(1) {
+        if count + size > max_players {
+            continue;
+        }
+
+        let mut target_guard = target.lock().unwrap();
+        let mut source_guard = source.lock().unwrap();
+        info!("Merging {} into {}", source_guard.name, target_guard.name);
+
+        let peers: Vec<Arc<Mutex<Peer>>> = real_peers!(source_guard).cloned().collect();
+        for peer in peers {
+            send_message(&mut peer.lock().unwrap(), "moving you to a bigger lobby...");
+            Server::peer_transfer(&mut source_guard, &mut target_guard, target.clone(), peer);
+        }
+
+        count += size;
+    }
+}
+
+// Private rooms are closed after staying empty for a minute
+fn close_rooms(servers: &[SubServer], empty_rooms: &mut HashMap<u16, u16>)
+{
+    for sub in servers.iter() {
+        if sub.code.is_none() {
+            continue;
+        }
+
+        if !sub.server.lock().unwrap().peers.read().unwrap().is_empty() {
+            empty_rooms.remove(&sub.port);
+            continue;
+        }
+
+        let timer = empty_rooms.entry(sub.port).or_insert(0);
+        *timer += 1;
+
+        if *timer < 120 {
+            continue;
+        }
+
+        empty_rooms.remove(&sub.port);
+        SERVERS.write().unwrap().retain(|x| x.port != sub.port);
+        sub.server.lock().unwrap().stop();
+        info!("Closed private room [{}]", sub.code.as_ref().unwrap());
+    }
+}
+
+fn is_free_lobby(sub: &SubServer) -> bool
+{
+    sub.code.is_none() && is_open_lobby(&sub.server.lock().unwrap())
+}
+
+fn is_open_lobby(server: &Server) -> bool
+{
+    server.state.lock().unwrap().name() == "Lobby" && !is_full(server)
+}
+
+fn is_full(server: &Server) -> bool
+{
+    server.peers.read().unwrap().len() >= config::get().rules(&server.name, None).max_players as usize
+}
+
+fn send_message(peer: &mut Peer, message: &str)
+{
+    let mut packet = Packet::new(PacketType::CLIENT_CHAT_MESSAGE);
+    packet.wu16(0);
+    packet.wstr(message);
+    peer.send(&mut packet);
+}
@@ -0,0 +1,111 @@
+// Reference master server: keeps a list of game servers that send heartbeats.
+// Run with `cargo run --bin master -- [bind address]`, send "list" datagram to get the list back.
+
+use std::collections::HashMap;
+use std::env;
+use std::net::{SocketAddr, UdpSocket};
+use std::time::{Duration, Instant};
+
+use serde::{Serialize, Deserialize};
+
+// Servers that missed this many seconds of heartbeats are dropped
+const EXPIRE_TIME: u64 = 90;
+
+#[derive(Serialize, Deserialize, Clone)]
+struct ServerInfo
+{
+    name: String,
+    state: String,
+    map: String,
+    players: Vec<String>,
+    max_players: u8
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct Heartbeat
+{
+    action: String,
+    address: String,
+    port: u16,
+    build: u16,
+    servers: Vec<ServerInfo>
+}
+
+#[derive(Serialize)]
+struct List
+{
+    servers: Vec<Heartbeat>
+}
+
+fn main()
+{
+    let bind = env::args().nth(1).unwrap_or("0.0.0.0:7700".to_string());
+    let socket = match UdpSocket::bind(&bind) {
+        Ok(res) => res,
+        Err(err) => {
+            println!("Failed to bind {}: {}", bind, err);
+            return;
+        }
+    };
+
+    let _ = socket.set_read_timeout(Some(Duration::from_secs(5)));
+    println!("Master server listening at {}/udp", bind);
+
+    let mut list: HashMap<SocketAddr, (Heartbeat, Instant)> = HashMap::new();
+    let mut buf = [0; 65535];
+
+    loop {
+        list.retain(|addr, entry| {
+            let alive = entry.1.elapsed().as_secs() < EXPIRE_TIME;
+            if !alive {
+                println!("{} expired", addr);
+            }
+
+            alive
+        });
+
+        let (size, src) = match socket.recv_from(&mut buf) {
+            Ok(res) => res,
+            Err(_) => continue
+        };
+
+        let text = String::from_utf8_lossy(&buf[..size]).to_string();
+        if text.trim() == "list" {
+            let value = toml::to_string(&List { servers: list.values().map(|x| x.0.clone()).collect() }).unwrap_or_default();
+            let _ = socket.send_to(value.as_bytes(), src);
+            continue;
+        }
+
+        let mut heartbeat: Heartbeat = match toml::from_str(&text) {
+            Ok(res) => res,
+            Err(err) => {
+                println!("Bad heartbeat from {}: {}", src, err);
+                continue;
+            }
+        };
+
+        // Server doesn't know it's public address
+        if heartbeat.address.is_empty() {
+            heartbeat.address = src.ip().to_string();
+        }
+
+        let key = SocketAddr::new(src.ip(), heartbeat.port);
+        match heartbeat.action.as_str() {
+            "register" => {
+                if !list.contains_key(&key) {
+                    println!("{} registered (build {}, {} servers)", key, heartbeat.build, heartbeat.servers.len());
+                }
+
+                list.insert(key, (heartbeat, Instant::now()));
+            },
+
+            "unregister" => {
+                if list.remove(&key).is_some() {
+                    println!("{} unregistered", key);
+                }
+            },
+
+            _ => println!("Unknown action from {}: {}", src, heartbeat.action)
+        }
+    }
+}
@@ -1,9 +1,58 @@
+use std::collections::HashMap;
 use std::fs;
+use std::process;
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::Duration;
 
-use serde::{Serialize, Deserialize};
+use log::{LevelFilter, info, warn, error};
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
 use lazy_static::lazy_static;
 
+use crate::args::ARGS;
+use crate::balancer;
+use crate::maps;
+
+// Explanations written above keys when saving the config
+const DOCS: [(&str, &str); 34] = [
+    ("gui", "Unused for now"),
+    ("debug", "Print debug messages"),
+    ("motd", "Message shown to players when they join a lobby"),
+    ("bans", "UDIDs of players that aren't allowed to join"),
+    ("server", "Changes to ports need a restart, everything else is applied on reload"),
+    ("server.tcp_port", "Main port, server info queries are answered on the same port over UDP"),
+    ("server.udp_port", "First sub-server's UDP port, next ones use the following ports"),
+    ("server.grow", "Start new sub-servers when existing ones are full"),
+    ("server.grow_limit", "Maximum amount of sub-servers (including private rooms)"),
+    ("master", "Optional registration in a server list"),
+    ("master.enabled", "Send heartbeats to the master server, the entry is removed on stop, SIGINT and SIGTERM"),
+    ("master.address", "Master server's host:port"),
+    ("master.public_address", "Address players should use, leave empty to let master server decide"),
+    ("master.interval", "Seconds between heartbeats"),
+    ("rules", "Gameplay rules, times are in seconds"),
+    ("rules.max_players", "Players per sub-server"),
+    ("rules.lobby_countdown", "Countdown after everyone in the lobby is ready"),
+    ("rules.vote_time", "Map vote length"),
+    ("rules.character_timeout", "Time to pick a character before getting kicked"),
+    ("rules.ability_cooldown", "Cooldown of survivor and exe abilities"),
+    ("rules.round_time", "Base round length"),
+    ("rules.round_time_bonus", "Extra round length with a full server, scaled by player count"),
+    ("rules.demonize_ratio", "Dead survivors are demonized while demons are fewer than this part of survivors"),
+    ("rules.exe_chance_start", "Exe chance range for new players (min, max exclusive)"),
+    ("rules.exe_chance_gain", "Exe chance gained after each round (min, max exclusive)"),
+    ("rules.exe_chance_reset", "Exe chance after playing as exe (min, max exclusive)"),
+    ("rules.exe_chance_max", "Highest exe chance"),
+    ("rules.servers", "Overrides for single sub-servers, e.g. [rules.servers.server1] max_players = 5"),
+    ("rules.maps", "Overrides for single maps by name or index, e.g. [rules.maps.\"Ravine Mist\"] round_time = 240"),
+    ("maps", "Map vote, maps are given by name or index"),
+    ("maps.pool", "Maps that can be voted for, empty for all of them"),
+    ("maps.cooldown", "Recently played maps that aren't offered again"),
+    ("maps.weights", "How often maps are offered, 1 by default, e.g. weights = { \"Green Hill\" = 3 }"),
+    ("maps.servers", "Overrides for single sub-servers, e.g. [maps.servers.server1] pool = [\"Act 9\"]"),
+];
+
 #[derive(Serialize, Deserialize)]
+#[serde(default)]
 pub(crate) struct ServerConfiguration 
 {
     pub tcp_port: u16,
@@ -13,69 +62,751 @@ pub(crate) struct ServerConfiguration
 }
 
 #[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct MasterConfiguration
+{
+    pub enabled: bool,
+    pub address: String,
+    pub public_address: String,
+    pub interval: u16
+}
+
+// Resolved with Configuration::rules, overrides are applied on top of the base rules
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub(crate) struct Rules
+{
+    pub max_players: u8,
+    pub lobby_countdown: u16,
+    pub vote_time: u16,
+    pub character_timeout: u16,
+    pub ability_cooldown: u16,
+    pub round_time: u16,
+    pub round_time_bonus: u16,
+    pub demonize_ratio: f32,
+    pub exe_chance_start: (u8, u8),
+    pub exe_chance_gain: (u8, u8),
+    pub exe_chance_reset: (u8, u8),
+    pub exe_chance_max: u8
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub(crate) struct RulesConfiguration
+{
+    #[serde(flatten)]
+    pub base: Rules,
+    pub servers: HashMap<String, toml::Table>, // By sub-server name
+    pub maps: HashMap<String, toml::Table> // By map name or index
+}
+
+// Maps offered in the vote, by name or index
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub(crate) struct MapPool
+{
+    pub pool: Vec<String>,
+    pub weights: HashMap<String, u32>,
+    pub cooldown: u8
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub(crate) struct MapsConfiguration
+{
+    #[serde(flatten)]
+    pub base: MapPool,
+    pub servers: HashMap<String, toml::Table> // By sub-server name
+}
+
+// Missing keys are taken from defaults, so older files keep working
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
 pub(crate) struct Configuration
 {
     pub server: ServerConfiguration,
     pub gui: bool,
-    pub debug: bool
+    pub debug: bool,
+    pub motd: String,
+    pub bans: Vec<String>, // Banned UDIDs
+    pub master: MasterConfiguration,
+    pub rules: RulesConfiguration,
+    pub maps: MapsConfiguration
+}
+
+impl Default for ServerConfiguration
+{
+    fn default() -> ServerConfiguration
+    {
+        ServerConfiguration {
+            tcp_port: 7606,
+            udp_port: 8606,
+            grow: false,
+            grow_limit: 32
+        }
+    }
+}
+
+impl Default for MasterConfiguration
+{
+    fn default() -> MasterConfiguration
+    {
+        MasterConfiguration {
+            enabled: false,
+            address: "127.0.0.1:7700".to_string(),
+            public_address: String::new(),
+            interval: 30
+        }
+    }
+}
+
+impl Default for Rules
+{
+    fn default() -> Rules
+    {
+        Rules {
+            max_players: 7,
+            lobby_countdown: 5,
+            vote_time: 20,
+            character_timeout: 30,
+            ability_cooldown: 10,
+            round_time: 180,
+            round_time_bonus: 100,
+            demonize_ratio: 0.5,
+            exe_chance_start: (2, 5),
+            exe_chance_gain: (2, 10),
+            exe_chance_reset: (0, 2),
+            exe_chance_max: 99
+        }
+    }
+}
+
+impl Rules
+{
+    fn validate(&self, path: &str, errors: &mut Vec<String>)
+    {
+        if self.max_players < 2 || self.max_players > 7 {
+            errors.push(format!("{}.max_players: must be between 2 and 7, got {}", path, self.max_players));
+        }
+
+        // Timers are kept in frames as u16
+        let times = [
+            ("lobby_countdown", self.lobby_countdown),
+            ("vote_time", self.vote_time),
+            ("ability_cooldown", self.ability_cooldown),
+            ("round_time", self.round_time.saturating_add(self.round_time_bonus))
+        ];
+
+        for (key, value) in times {
+            if value as u32 * 60 > u16::MAX as u32 {
+                errors.push(format!("{}.{}: too long, at most {} seconds", path, key, u16::MAX / 60));
+            }
+        }
+
+        if self.lobby_countdown < 1 || self.vote_time < 1 || self.character_timeout < 1 || self.round_time < 1 {
+            errors.push(format!("{}: times can't be 0", path));
+        }
+
+        if !(0.0..=1.0).contains(&self.demonize_ratio) {
+            errors.push(format!("{}.demonize_ratio: must be between 0 and 1, got {}", path, self.demonize_ratio));
+        }
+
+        let ranges = [
+            ("exe_chance_start", self.exe_chance_start),
+            ("exe_chance_gain", self.exe_chance_gain),
+            ("exe_chance_reset", self.exe_chance_reset)
+        ];
+
+        for (key, (min, max)) in ranges {
+            if min >= max {
+                errors.push(format!("{}.{}: first value must be lower than the second, got [{}, {}]", path, key, min, max));
+            }
+        }
+
+        // Exe is chosen by weight, someone must have a chance
+        if self.exe_chance_max < 1 || self.exe_chance_max > 100 {
+            errors.push(format!("{}.exe_chance_max: must be between 1 and 100, got {}", path, self.exe_chance_max));
+        }
+    }
+}
+
+impl Default for MapPool
+{
+    fn default() -> MapPool
+    {
+        MapPool {
+            pool: Vec::new(),
+            weights: HashMap::new(),
+            cooldown: 1
+        }
+    }
+}
+
+impl MapPool
+{
+    // Weight of a map that's in the pool, 0 if it isn't
+    pub fn weight(&self, name: &str, index: usize) -> u32
+    {
+        let matches = |key: &String| key.trim().eq_ignore_ascii_case(name) || key.trim() == index.to_string();
+
+        if !self.pool.is_empty() && !self.pool.iter().any(matches) {
+            return 0;
+        }
+
+        match self.weights.iter().find(|x| matches(x.0)) {
+            Some(res) => *res.1,
+            None => 1
+        }
+    }
+
+    fn validate(&self, path: &str, errors: &mut Vec<String>)
+    {
+        for key in self.pool.iter().chain(self.weights.keys()) {
+            if maps::find(key).is_none() {
+                errors.push(format!("{}: unknown map \"{}\"", path, key));
+            }
+        }
+
+        let available = maps::list().iter().filter(|x| self.weight(&x.1, x.0) > 0).count();
+
+        if available == 0 {
+            errors.push(format!("{}: no maps left to vote for", path));
+        }
+    }
+}
+
+impl Default for Configuration
+{
+    fn default() -> Configuration
+    {
+        Configuration { 
+            server: ServerConfiguration::default(),
+            gui: true,
+            debug: true,
+            motd: "type .help for more info".to_string(),
+            bans: Vec::new(),
+            master: MasterConfiguration::default(),
+            rules: RulesConfiguration::default(),
+            maps: MapsConfiguration::default()
+        }
+    }
 }
 
-const DEFAULT_CONFIG: Configuration = Configuration { 
-    server: ServerConfiguration {
-        tcp_port: 7606,
-        udp_port: 8606,
-        grow: false,
-        grow_limit: 32
-    },
+impl Configuration
+{
+    pub fn log_level(&self) -> LevelFilter
+    {
+        if self.debug {
+            LevelFilter::Debug
+        }
+        else {
+            LevelFilter::Info
+        }
+    }
 
-    gui: true,
-    debug: true,
-};
+    // Rules for a sub-server, with map overrides if it's known
+    pub fn rules(&self, server: &str, map: Option<&str>) -> Rules
+    {
+        let mut overrides = Vec::new();
+        overrides.extend(self.rules.servers.get(server));
+
+        // Keys are given by name or index, same as maps.pool
+        if let Some(map) = map {
+            overrides.extend(self.rules.maps.iter().find(|x| maps::find(x.0).is_some_and(|x| x.1 == map)).map(|x| x.1));
+        }
+
+        match Self::merge(&self.rules.base, &overrides) {
+            Ok(res) => res,
+            Err(_) => self.rules.base.clone() // Checked in validate
+        }
+    }
+
+    // Map pool for a sub-server
+    pub fn maps(&self, server: &str) -> MapPool
+    {
+        let overrides: Vec<&toml::Table> = self.maps.servers.get(server).into_iter().collect();
+
+        match Self::merge(&self.maps.base, &overrides) {
+            Ok(res) => res,
+            Err(_) => self.maps.base.clone() // Checked in validate
+        }
+    }
+
+    // Keys of overrides replace the base ones, tables included
+    fn merge<T: Serialize + DeserializeOwned>(base: &T, overrides: &[&toml::Table]) -> Result<T, String>
+    {
+        let mut table = toml::Table::try_from(base).map_err(|x| x.to_string())?;
+        for value in overrides {
+            for (key, value) in value.iter() {
+                if !table.contains_key(key) {
+                    return Err(format!("unknown key \"{}\"", key));
+                }
+
+                table.insert(key.clone(), value.clone());
+            }
+        }
+
+        toml::Value::Table(table).try_into().map_err(|x: toml::de::Error| x.message().to_string())
+    }
+
+    fn validate(&self) -> Result<(), String>
+    {
+        let mut errors = Vec::new();
+
+        self.rules.base.validate("rules", &mut errors);
+
+        let mut seen = Vec::new();
+        for name in self.rules.maps.keys() {
+            match maps::find(name) {
+                Some((_, map)) => {
+                    if seen.contains(&map) {
+                        errors.push(format!("rules.maps.\"{}\": {} already has overrides", name, map));
+                    }
+
+                    seen.push(map);
+                },
+                None => errors.push(format!("rules.maps.\"{}\": unknown map", name))
+            }
+        }
+
+        let overrides = self.rules.servers.iter().map(|x| ("servers", x)).chain(self.rules.maps.iter().map(|x| ("maps", x)));
+        for (kind, (name, value)) in overrides {
+            let path = format!("rules.{}.\"{}\"", kind, name);
+            match Self::merge(&self.rules.base, &[value]) {
+                Ok(res) => res.validate(&path, &mut errors),
+                Err(err) => errors.push(format!("{}: {}", path, err))
+            }
+        }
+
+        self.maps.base.validate("maps", &mut errors);
+
+        for (name, value) in self.maps.servers.iter() {
+            let path = format!("maps.servers.\"{}\"", name);
+            match Self::merge(&self.maps.base, &[value]) {
+                Ok(res) => res.validate(&path, &mut errors),
+                Err(err) => errors.push(format!("{}: {}", path, err))
+            }
+        }
+
+        if self.server.tcp_port == 0 {
+            errors.push("server.tcp_port: can't be 0".to_string());
+        }
+
+        if self.server.udp_port == 0 {
+            errors.push("server.udp_port: can't be 0".to_string());
+        }
+
+        // Every sub-server needs it's own port
+        let max_limit = u16::MAX - self.server.udp_port.max(1) + 1;
+        if self.server.grow_limit < 1 || self.server.grow_limit > max_limit {
+            errors.push(format!("server.grow_limit: must be between 1 and {} (with udp_port {}), got {}", max_limit, self.server.udp_port, self.server.grow_limit));
+        }
+
+        let last_port = self.server.udp_port as u32 + self.server.grow_limit as u32;
+        if (self.server.udp_port as u32..last_port).contains(&(self.server.tcp_port as u32)) {
+            errors.push(format!("server.tcp_port: {} is taken by sub-servers ({}-{}/udp)", self.server.tcp_port, self.server.udp_port, last_port - 1));
+        }
+
+        if self.master.enabled && self.master.address.is_empty() {
+            errors.push("master.address: can't be empty when master.enabled is set".to_string());
+        }
+
+        if self.master.interval < 1 {
+            errors.push("master.interval: must be at least 1".to_string());
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        }
+        else {
+            Err(errors.join("\n"))
+        }
+    }
+}
 
 lazy_static! {
-    pub(crate) static ref CONFIG: Configuration = init_config();
+    static ref CONFIG: RwLock<Arc<Configuration>> = RwLock::new(Arc::new(init_config()));
+}
+
+// Keep the returned snapshot for the whole operation, config may be reloaded meanwhile
+pub(crate) fn get() -> Arc<Configuration>
+{
+    CONFIG.read().unwrap().clone()
 }
 
-fn default_config() -> Configuration
+pub(crate) fn reload() -> bool
 {
-    let value = match toml::to_string(&DEFAULT_CONFIG)
+    let result = match fs::read_to_string(&ARGS.config_path)
     {
         Ok(res) => res,
         Err(err) => {
-            println!("Failed to shit: {}", err);
-            return DEFAULT_CONFIG
+            error!("Failed to open config file: {}", err);
+            return false;
         }
     };
 
-    match fs::write("Config.toml", value.clone())
+    for key in unknown_keys(&result) {
+        warn!("Unknown key \"{}\" in config, ignored", key);
+    }
+
+    let mut config = match parse(&result)
     {
-        Ok(_) => {},
+        Ok(res) => res,
         Err(err) => {
-            println!("Failed to save default configuration: {}", err);
+            error!("Invalid config, keeping the old one:\n{}", err);
+            return false;
         }
+    };
+
+    // Sockets are already bound
+    let current = get();
+    if config.server.tcp_port != current.server.tcp_port {
+        warn!("server.tcp_port changed ({} -> {}), restart is required to apply it!", current.server.tcp_port, config.server.tcp_port);
+        config.server.tcp_port = current.server.tcp_port;
     }
 
-    DEFAULT_CONFIG
+    if config.server.udp_port != current.server.udp_port {
+        warn!("server.udp_port changed ({} -> {}), restart is required to apply it!", current.server.udp_port, config.server.udp_port);
+        config.server.udp_port = current.server.udp_port;
+    }
+
+    log::set_max_level(config.log_level());
+    *CONFIG.write().unwrap() = Arc::new(config);
+
+    info!("Configuration reloaded.");
+    balancer::kick_banned();
+    true
+}
+
+// Reloads config when the file changes
+pub(crate) fn watch()
+{
+    let _ = thread::Builder::new().name("config".to_string()).spawn(move || {
+        let modified = || fs::metadata(&ARGS.config_path).and_then(|x| x.modified()).ok();
+        let mut last = modified();
+
+        loop {
+            thread::sleep(Duration::from_secs(2));
+
+            let time = modified();
+            if time.is_some() && time != last {
+                last = time;
+                reload();
+            }
+        }
+    });
+}
+
+// Errors from toml already contain line and column
+fn parse(text: &str) -> Result<Configuration, String>
+{
+    let mut config: Configuration = toml::from_str(text).map_err(|x| x.to_string())?;
+    ARGS.overrides.apply(&mut config);
+    config.validate()?;
+    Ok(config)
+}
+
+// Typos would be silently replaced with defaults otherwise
+fn unknown_keys(text: &str) -> Vec<String>
+{
+    let value: toml::Value = match toml::from_str(text) {
+        Ok(res) => res,
+        Err(_) => return Vec::new()
+    };
+
+    let known = match toml::Value::try_from(Configuration::default()) {
+        Ok(res) => res,
+        Err(_) => return Vec::new()
+    };
+
+    let mut result = Vec::new();
+    collect_unknown(&value, &known, "", &mut result);
+    result
+}
+
+fn collect_unknown(value: &toml::Value, known: &toml::Value, path: &str, result: &mut Vec<String>)
+{
+    let (table, known) = match (value.as_table(), known.as_table()) {
+        (Some(table), Some(known)) => (table, known),
+        _ => return
+    };
+
+    // Tables without default entries are free-form
+    if known.is_empty() {
+        return;
+    }
+
+    for (key, value) in table {
+        let full = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+
+        match known.get(key) {
+            Some(known) => collect_unknown(value, known, &full, result),
+            None => result.push(full)
+        }
+    }
+}
+
+// Effective configuration, as --print-config shows it
+pub(crate) fn dump() -> String
+{
+    match commented(&get()) {
+        Ok(res) => res,
+        Err(err) => format!("# Failed to serialize configuration: {}", err)
+    }
+}
+
+// Config with explanations above the keys
+fn commented(config: &Configuration) -> Result<String, String>
+{
+    let value = toml::to_string(config).map_err(|x| x.to_string())?;
+    let mut result = String::new();
+    let mut section = String::new();
+
+    for line in value.lines() {
+        let trimmed = line.trim();
+        let key = if trimmed.starts_with('[') {
+            section = trimmed.trim_matches(|x| x == '[' || x == ']').to_string();
+            section.clone()
+        }
+        else {
+            match trimmed.split_once('=') {
+                Some((key, _)) if section.is_empty() => key.trim().to_string(),
+                Some((key, _)) => format!("{}.{}", section, key.trim()),
+                None => String::new()
+            }
+        };
+
+        if let Some(doc) = DOCS.iter().find(|x| x.0 == key) {
+            result.push_str(&format!("# {}\n", doc.1));
+        }
+
+        result.push_str(line);
+        result.push('\n');
+    }
+
+    Ok(result)
+}
+
+fn save(config: &Configuration) -> bool
+{
+    let value = match commented(config)
+    {
+        Ok(res) => res,
+        Err(err) => {
+            eprintln!("Failed to serialize configuration: {}", err);
+            return false;
+        }
+    };
+
+    match fs::write(&ARGS.config_path, value)
+    {
+        Ok(_) => true,
+        Err(err) => {
+            eprintln!("Failed to save configuration: {}", err);
+            false
+        }
+    }
 }
 
 fn init_config() -> Configuration
 {
-    let result = match fs::read_to_string("Config.toml")
+    let result = match fs::read_to_string(&ARGS.config_path)
+    {
+        Ok(res) => res,
+        Err(err) => {
+            eprintln!("Failed to open config file: {}", err);
+
+            // --print-config shouldn't touch the disk
+            let mut config = Configuration::default();
+            if !ARGS.print_config {
+                save(&config);
+            }
+
+            ARGS.overrides.apply(&mut config);
+            return config;
+        }
+    };
+
+    for key in unknown_keys(&result) {
+        eprintln!("Unknown key \"{}\" in config, ignored", key);
+    }
+
+    let config = match parse(&result)
     {
         Ok(res) => res,
         Err(err) => {
-            println!("Failed to open config file: {}", err);
-            return default_config();
+            eprintln!("Invalid {}:\n{}", ARGS.config_path, err);
+
+            if !ARGS.allow_invalid_config {
+                eprintln!("Fix the file or start with --allow-invalid-config to use defaults.");
+                process::exit(1);
+            }
+
+            eprintln!("Using default configuration.");
+
+            let mut config = Configuration::default();
+            ARGS.overrides.apply(&mut config);
+            return config;
         }
     };
-        
-    // we only assign once, hence we dont care
-    match toml::from_str(&result)
+
+    config
+}
+
+// Rewrites the file with all current keys and comments
+// Overrides aren't written to the file
+pub(crate) fn migrate() -> bool
+{
+    let text = match fs::read_to_string(&ARGS.config_path)
     {
-        Ok(result) => result,
+        Ok(res) => res,
         Err(err) => {
-            println!("Failed to parase config: {}", err);
-            return DEFAULT_CONFIG;
+            eprintln!("Failed to open config file: {}", err);
+            return false;
         }
+    };
+
+    for key in unknown_keys(&text) {
+        eprintln!("Unknown key \"{}\" in config, dropped", key);
+    }
+
+    if let Err(err) = parse(&text) {
+        eprintln!("Invalid {}:\n{}", ARGS.config_path, err);
+        return false;
+    }
+
+    let file: Configuration = match toml::from_str(&text) {
+        Ok(res) => res,
+        Err(_) => return false
+    };
+
+    let backup = format!("{}.bak", ARGS.config_path);
+    if let Err(err) = fs::copy(&ARGS.config_path, &backup) {
+        eprintln!("Failed to back up {}: {}", ARGS.config_path, err);
+        return false;
+    }
+
+    if !save(&file) {
+        return false;
+    }
+
+    println!("{} migrated, old file saved as {}", ARGS.config_path, backup);
+    true
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn config(text: &str) -> Configuration
+    {
+        toml::from_str(text).unwrap()
+    }
+
+    fn table(text: &str) -> toml::Table
+    {
+        toml::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn merge_overrides_in_order()
+    {
+        let base = Rules::default();
+        let rules: Rules = Configuration::merge(&base, &[&table("round_time = 100\nvote_time = 5"), &table("round_time = 200")]).unwrap();
+
+        assert_eq!(rules.round_time, 200);
+        assert_eq!(rules.vote_time, 5);
+        assert_eq!(rules.max_players, base.max_players);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn merge_rejects_unknown_keys()
+    {
+        let result: Result<Rules, String> = Configuration::merge(&Rules::default(), &[&table("round_tim = 100")]);
+        assert!(result.is_err_and(|x| x.contains("round_tim")));
+
+        let result: Result<Rules, String> = Configuration::merge(&Rules::default(), &[&table("round_time = \"long\"")]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn rules_resolve_map_keys()
+    {
+        let config = config("[rules]\nround_time = 100\n[rules.servers.server1]\nround_time = 150\nvote_time = 7\n[rules.maps.\"ravine mist\"]\nround_time = 200\n[rules.maps.\"3\"]\nround_time = 250");
+
+        assert_eq!(config.rules("server0", None).round_time, 100);
+        assert_eq!(config.rules("server1", None).round_time, 150);
+        assert_eq!(config.rules("server1", Some("Ravine Mist")).round_time, 200);
+        assert_eq!(config.rules("server1", Some("Ravine Mist")).vote_time, 7);
+        assert_eq!(config.rules("server0", Some("Desert Town")).round_time, 250);
+        assert_eq!(config.rules("server0", Some("Hide and Seek 2")).round_time, 100);
+    }
+
+    #[test]
+    fn validate_defaults()
+    {
+        assert!(Configuration::default().validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rules()
+    {
+        let err = config("[rules]\nmax_players = 9\ndemonize_ratio = 2.0").validate().unwrap_err();
+        assert!(err.contains("rules.max_players"));
+        assert!(err.contains("rules.demonize_ratio"));
+
+        let err = config("[rules.servers.server1]\nexe_chance_gain = [5, 2]").validate().unwrap_err();
+        assert!(err.contains("rules.servers.\"server1\".exe_chance_gain"));
+
+        let err = config("[rules.maps.\"Ravine Myst\"]\nround_time = 200").validate().unwrap_err();
+        assert!(err.contains("unknown map"));
+
+        let err = config("[rules.maps.\"Ravine Mist\"]\nround_time = 200\n[rules.maps.\"1\"]\nround_time = 100").validate().unwrap_err();
+        assert!(err.contains("already has overrides"));
+    }
+
+    #[test]
+    fn validate_ports()
+    {
+        let err = config("[server]\ntcp_port = 7610\nudp_port = 7600\ngrow_limit = 20").validate().unwrap_err();
+        assert!(err.contains("server.tcp_port"));
+
+        assert!(config("[server]\ntcp_port = 7620\nudp_port = 7600\ngrow_limit = 20").validate().is_ok());
+
+        let err = config("[server]\nudp_port = 65530\ngrow_limit = 10").validate().unwrap_err();
+        assert!(err.contains("server.grow_limit"));
+
+        let err = config("[server]\ngrow_limit = 0").validate().unwrap_err();
+        assert!(err.contains("server.grow_limit"));
+    }
+
+    #[test]
+    fn map_pool_weights()
+    {
+        let pool: MapPool = toml::from_str("pool = [\"ravine MIST\", \" 0 \"]\nweights = { \"RAVINE MIST\" = 3 }").unwrap();
+
+        assert_eq!(pool.weight("Ravine Mist", 1), 3);
+        assert_eq!(pool.weight("Hide and Seek 2", 0), 1);
+        assert_eq!(pool.weight("Desert Town", 3), 0);
+
+        // Empty pool has every map
+        let pool: MapPool = toml::from_str("weights = { \"3\" = 5 }").unwrap();
+        assert_eq!(pool.weight("Desert Town", 3), 5);
+        assert_eq!(pool.weight("Ravine Mist", 1), 1);
+    }
+
+    #[test]
+    fn validate_map_pool()
+    {
+        let err = config("[maps]\npool = [\"Nope\"]").validate().unwrap_err();
+        assert!(err.contains("unknown map \"Nope\""));
+        assert!(err.contains("no maps left"));
+
+        let err = config("[maps.servers.server1]\nweights = { \"1\" = 0, \"Ravine Mist\" = 0 }\npool = [\"1\"]").validate().unwrap_err();
+        assert!(err.contains("maps.servers.\"server1\""));
+
+        let err = config("[maps.servers.server1]\npol = [\"1\"]").validate().unwrap_err();
+        assert!(err.contains("unknown key"));
+    }
+}
@@ -0,0 +1,141 @@
+use std::io::stdin;
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use log::{info, warn};
+
+use crate::{balancer::{self, SERVERS}, config, heartbeat};
+
+pub(crate) fn start()
+{
+    let _ = thread::Builder::new().name("console".to_string()).spawn(move || {
+        for line in stdin().lines() {
+            let line = match line {
+                Ok(res) => res,
+                Err(_) => break
+            };
+
+            handle(line.trim());
+        }
+    });
+
+    signals::install();
+    let _ = thread::Builder::new().name("signals".to_string()).spawn(move || {
+        while !STOP.load(Ordering::SeqCst) {
+            thread::sleep(Duration::from_millis(100));
+        }
+
+        // Second signal kills us if shutting down hangs
+        signals::restore();
+        info!("Got stop signal");
+        stop();
+    });
+}
+
+fn handle(command: &str)
+{
+    match command
+    {
+        "" => {},
+
+        "help" => {
+            info!("status - list sub-servers");
+            info!("room - create a private room");
+            info!("reload - reload config file");
+            info!("stop - shut the server down");
+        },
+
+        "status" => {
+            let servers = SERVERS.read().unwrap().clone();
+
+            for sub in servers {
+                let server = sub.server.lock().unwrap();
+                let state = server.state.lock().unwrap().name().to_string();
+                let count = server.peers.read().unwrap().len();
+
+                match sub.code {
+                    Some(code) => info!("{} ({}/udp) [{}]: {} players, private [{}]", server.name, sub.port, state, count, code),
+                    None => info!("{} ({}/udp) [{}]: {} players", server.name, sub.port, state, count)
+                }
+            }
+        },
+
+        "room" => {
+            match balancer::create_room() {
+                Some(code) => info!("Room code is {}", code),
+                None => warn!("Couldn't create a room.")
+            }
+        },
+
+        "reload" => {
+            config::reload();
+        },
+
+        "stop" => stop(),
+
+        _ => warn!("Unknown command \"{}\", type help for the list.", command)
+    }
+}
+
+fn stop()
+{
+    heartbeat::unregister();
+
+    // Never hold the list lock while locking servers
+    let servers = SERVERS.read().unwrap().clone();
+    for sub in servers {
+        sub.server.lock().unwrap().stop();
+    }
+
+    info!("Bye!");
+    process::exit(0);
+}
+
+// Set from the signal handler, which can't do anything else safely
+static STOP: AtomicBool = AtomicBool::new(false);
+
+// SIGINT and SIGTERM (container stop) shut down like the stop command
+#[cfg(unix)]
+mod signals
+{
+    use std::sync::atomic::Ordering;
+
+    const SIGINT: i32 = 2;
+    const SIGTERM: i32 = 15;
+    const SIG_DFL: usize = 0;
+
+    extern "C" {
+        fn signal(signum: i32, handler: usize) -> usize;
+    }
+
+    extern "C" fn handler(_signum: i32)
+    {
+        super::STOP.store(true, Ordering::SeqCst);
+    }
+
+    pub fn install()
+    {
+        unsafe {
+            signal(SIGINT, handler as extern "C" fn(i32) as usize);
+            signal(SIGTERM, handler as extern "C" fn(i32) as usize);
+        }
+    }
+
+    pub fn restore()
+    {
+        unsafe {
+            signal(SIGINT, SIG_DFL);
+            signal(SIGTERM, SIG_DFL);
+        }
+    }
+}
+
+// Ctrl+C on Windows just kills the process, use the stop command there
+#[cfg(not(unix))]
+mod signals
+{
+    pub fn install() {}
+    pub fn restore() {}
+}
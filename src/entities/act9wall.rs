@@ -0,0 +1,79 @@
+use crate::{entity::Entity, states::game::{Game, GameTimer}, server::Server, packet::{Packet, PacketType}};
+
+pub(crate) const WALL_WARNING: u16 = 5 * 60;
+
+#[derive(Copy, Clone, PartialEq)]
+pub(crate) enum WallState
+{
+    Open,
+    Closing,
+    Closed
+}
+
+// Cuts off part of the map once round time goes below close_at
+pub(crate) struct Act9Wall
+{
+    pub id: u8,
+    pub close_at: u16,
+    pub state: WallState
+}
+
+impl Entity for Act9Wall
+{
+    fn spawn(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        Some(self.packet(*id))
+    }
+
+    fn tick(&mut self, server: &mut Server, game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        let time = game.timer.get(GameTimer::RoundTime);
+        let state = if time <= self.close_at {
+            WallState::Closed
+        }
+        else if time <= self.close_at.saturating_add(WALL_WARNING) {
+            WallState::Closing
+        }
+        else {
+            WallState::Open
+        };
+
+        if state == self.state {
+            return None;
+        }
+
+        // Collision depends on it, so it can't get lost
+        self.state = state;
+        server.multicast_real(&mut self.packet(*id));
+        None
+    }
+
+    fn destroy(&mut self, _server: &mut Server, _game: &mut Game, _id: &u16) -> Option<Packet> 
+    {
+        None
+    }
+
+    fn id(&self) -> &str {
+        "act9wall"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl Act9Wall
+{
+    fn packet(&self, id: u16) -> Packet
+    {
+        let mut packet = Packet::new(PacketType::SERVER_ACT9WALL_STATE);
+        packet.wu16(id);
+        packet.wu8(self.id);
+        packet.wu8(self.state as u8);
+        packet
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{entity::Entity, states::game::Game, server::Server, packet::{Packet, PacketType}};
+
+pub(crate) const ASS_COOLDOWN: u16 = 20 * 60;
+
+// Bell survivors can ring to stun the doll
+pub(crate) struct DTAss
+{
+    pub id: u8,
+    pub x: u16,
+    pub y: u16,
+    pub cooldown: u16
+}
+
+impl Entity for DTAss
+{
+    fn spawn(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        Some(self.state(*id, 0))
+    }
+
+    fn tick(&mut self, server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        if self.cooldown == 0 {
+            return None;
+        }
+
+        self.cooldown -= 1;
+        if self.cooldown > 0 {
+            return None;
+        }
+
+        server.multicast_real(&mut self.state(*id, 0));
+        None
+    }
+
+    fn destroy(&mut self, _server: &mut Server, _game: &mut Game, _id: &u16) -> Option<Packet> 
+    {
+        None
+    }
+
+    fn id(&self) -> &str {
+        "dtass"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl DTAss
+{
+    pub fn activate(&mut self, id: u16, by: u16) -> Packet
+    {
+        self.cooldown = ASS_COOLDOWN;
+        self.state(id, by)
+    }
+
+    fn state(&self, id: u16, by: u16) -> Packet
+    {
+        let mut packet = Packet::new(PacketType::SERVER_DTASS_STATE);
+        packet.wu16(id);
+        packet.wu8(self.id);
+        packet.wu8((self.cooldown == 0) as u8);
+        packet.wu16(by);
+        packet
+    }
+}
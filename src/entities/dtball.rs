@@ -0,0 +1,65 @@
+use crate::{entity::Entity, states::game::Game, server::Server, packet::{Packet, PacketType}};
+
+// Tumbleweed ball rolling down the street
+pub(crate) struct DTBall
+{
+    pub x: f32,
+    pub y: f32,
+    pub dir: i8,
+    pub timer: u16
+}
+
+impl Entity for DTBall
+{
+    fn spawn(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        let mut packet = Packet::new(PacketType::SERVER_DTBALL_STATE);
+        packet.wu8(0);
+        packet.wu16(*id);
+        packet.wu16(self.x as u16);
+        packet.wu16(self.y as u16);
+        packet.wi8(self.dir);
+
+        Some(packet)
+    }
+
+    fn tick(&mut self, _server: &mut Server, game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        self.x += self.dir as f32 * 5.0;
+
+        self.timer -= 1;
+        if self.timer == 0 || self.x <= 0.0 {
+            game.queue_destroy(id);
+            return None;
+        }
+
+        let mut packet = Packet::new(PacketType::SERVER_DTBALL_STATE);
+        packet.wu8(1);
+        packet.wu16(*id);
+        packet.wu16(self.x as u16);
+        packet.wu16(self.y as u16);
+
+        Some(packet)
+    }
+
+    fn destroy(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        let mut packet = Packet::new(PacketType::SERVER_DTBALL_STATE);
+        packet.wu8(2);
+        packet.wu16(*id);
+
+        Some(packet)
+    }
+
+    fn id(&self) -> &str {
+        "dtball"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{entity::Entity, states::game::Game, server::{Server, real_peers}, packet::{Packet, PacketType}};
+
+pub(crate) const DOLL_SPEED: f32 = 2.5;
+
+// Floats towards the closest survivor, can be stunned for a while
+pub(crate) struct DTTailsDoll
+{
+    pub x: f32,
+    pub y: f32,
+    pub target: u16,
+    pub stun: u16,
+    pub sync_timer: u8
+}
+
+impl Entity for DTTailsDoll
+{
+    fn spawn(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        Some(self.state(0, *id))
+    }
+
+    fn tick(&mut self, server: &mut Server, game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        self.sync_timer = self.sync_timer.saturating_sub(1);
+
+        if self.stun > 0 {
+            self.stun -= 1;
+            return None;
+        }
+
+        // Closest living survivor
+        let mut target = None;
+        for peer in real_peers!(server) {
+            let peer = peer.lock().unwrap();
+            let player = match &peer.player {
+                Some(res) => res,
+                None => continue
+            };
+
+            if player.exe || player.dead || player.escaped || player.revival_times >= 2 {
+                continue;
+            }
+
+            let pos = match game.players_pos.get(&peer.id()) {
+                Some(res) if *res != (0.0, 0.0) => *res,
+                _ => continue
+            };
+
+            let dist = ((pos.0 - self.x).powi(2) + (pos.1 - self.y).powi(2)).sqrt();
+            let closer = match target {
+                Some((_, _, best)) => dist < best,
+                None => true
+            };
+
+            if closer {
+                target = Some((peer.id(), pos, dist));
+            }
+        }
+
+        if let Some((pid, pos, dist)) = target {
+            self.target = pid;
+
+            if dist > DOLL_SPEED {
+                self.x += (pos.0 - self.x) / dist * DOLL_SPEED;
+                self.y += (pos.1 - self.y) / dist * DOLL_SPEED;
+            }
+        }
+
+        if self.sync_timer > 0 {
+            return None;
+        }
+
+        self.sync_timer = 3;
+        Some(self.state(1, *id))
+    }
+
+    fn destroy(&mut self, _server: &mut Server, _game: &mut Game, _id: &u16) -> Option<Packet> 
+    {
+        None
+    }
+
+    fn id(&self) -> &str {
+        "dttailsdoll"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl DTTailsDoll
+{
+    pub fn state(&self, tp: u8, id: u16) -> Packet
+    {
+        let mut packet = Packet::new(PacketType::SERVER_DTTAILSDOLL_STATE);
+        packet.wu8(tp);
+        packet.wu16(id);
+        packet.wu16(self.x as u16);
+        packet.wu16(self.y as u16);
+        packet.wu16(self.target);
+        packet.wu8((self.stun > 0) as u8);
+        packet
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{entity::Entity, states::game::Game, server::Server, packet::{Packet, PacketType}};
+
+pub(crate) const FART_ACTIVE: u16 = 60;
+pub(crate) const FART_COOLDOWN: u16 = 4 * 60;
+
+// Gas vent that pushes everyone around it away while active
+pub(crate) struct Fart
+{
+    pub id: u8,
+    pub x: u16,
+    pub y: u16,
+    pub active: u16,
+    pub cooldown: u16
+}
+
+impl Entity for Fart
+{
+    fn spawn(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        Some(self.state(*id, 0))
+    }
+
+    fn tick(&mut self, server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        self.cooldown = self.cooldown.saturating_sub(1);
+
+        if self.active == 0 {
+            return None;
+        }
+
+        self.active -= 1;
+        if self.active > 0 {
+            return None;
+        }
+
+        server.multicast_real(&mut self.state(*id, 0));
+        None
+    }
+
+    fn destroy(&mut self, _server: &mut Server, _game: &mut Game, _id: &u16) -> Option<Packet> 
+    {
+        None
+    }
+
+    fn id(&self) -> &str {
+        "fart"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl Fart
+{
+    pub fn push(&mut self, id: u16, by: u16) -> Packet
+    {
+        self.active = FART_ACTIVE;
+        self.cooldown = FART_COOLDOWN;
+        self.state(id, by)
+    }
+
+    fn state(&self, id: u16, by: u16) -> Packet
+    {
+        let mut packet = Packet::new(PacketType::SERVER_FART_STATE);
+        packet.wu16(id);
+        packet.wu8(self.id);
+        packet.wu16(self.x);
+        packet.wu16(self.y);
+        packet.wu8((self.active > 0) as u8);
+        packet.wu16(by);
+        packet
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{entity::Entity, states::game::Game, server::Server, packet::{Packet, PacketType}};
+
+pub(crate) const THUNDER_WARNING: u16 = 2 * 60;
+pub(crate) const THUNDER_STRIKE: u16 = 30; // How long the strike hurts
+
+// Lightning bolt, clouds gather above the spot before it strikes
+pub(crate) struct GHZThunder
+{
+    pub x: u16,
+    pub y: u16,
+    pub struck: bool,
+    pub timer: u16
+}
+
+impl Entity for GHZThunder
+{
+    fn spawn(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        self.timer = THUNDER_WARNING;
+        Some(self.state(*id))
+    }
+
+    fn tick(&mut self, server: &mut Server, game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        self.timer -= 1;
+        if self.timer > 0 {
+            return None;
+        }
+
+        if self.struck {
+            game.queue_destroy(id);
+            return None;
+        }
+
+        self.struck = true;
+        self.timer = THUNDER_STRIKE;
+        server.multicast_real(&mut self.state(*id));
+        None
+    }
+
+    fn destroy(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        let mut packet = Packet::new(PacketType::SERVER_GHZTHUNDER_STATE);
+        packet.wu8(2);
+        packet.wu16(*id);
+        Some(packet)
+    }
+
+    fn id(&self) -> &str {
+        "ghzthunder"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl GHZThunder
+{
+    fn state(&self, id: u16) -> Packet
+    {
+        let mut packet = Packet::new(PacketType::SERVER_GHZTHUNDER_STATE);
+        packet.wu8(self.struck as u8);
+        packet.wu16(id);
+        packet.wu16(self.x);
+        packet.wu16(self.y);
+        packet
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{entity::Entity, states::game::Game, server::Server, packet::{Packet, PacketType}};
+
+pub(crate) const DOOR_COOLDOWN: u16 = 90;
+
+pub(crate) struct HDDoor
+{
+    pub id: u8,
+    pub open: bool,
+    pub cooldown: u16
+}
+
+impl Entity for HDDoor
+{
+    // Synced by the map, see HauntingDream::peer_ready
+    fn spawn(&mut self, _server: &mut Server, _game: &mut Game, _id: &u16) -> Option<Packet> 
+    {
+        None
+    }
+
+    fn tick(&mut self, _server: &mut Server, _game: &mut Game, _id: &u16) -> Option<Packet> 
+    {
+        self.cooldown = self.cooldown.saturating_sub(1);
+        None
+    }
+
+    fn destroy(&mut self, _server: &mut Server, _game: &mut Game, _id: &u16) -> Option<Packet> 
+    {
+        None
+    }
+
+    fn id(&self) -> &str {
+        "hddoor"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl HDDoor
+{
+    pub fn toggle(&mut self)
+    {
+        self.open = !self.open;
+        self.cooldown = DOOR_COOLDOWN;
+    }
+
+    pub fn state(&self, id: u16) -> Packet
+    {
+        let mut packet = Packet::new(PacketType::SERVER_HDDOOR_STATE);
+        packet.wu16(id);
+        packet.wu8(self.id);
+        packet.wu8(self.open as u8);
+        packet
+    }
+}
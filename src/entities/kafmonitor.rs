@@ -0,0 +1,97 @@
+use rand::{thread_rng, Rng};
+
+use crate::{entity::Entity, states::game::Game, server::Server, packet::{Packet, PacketType}};
+
+pub(crate) const MONITOR_RESPAWN: u16 = 30 * 60;
+
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum MonitorReward
+{
+    Rings,
+    Shield,
+    Speed,
+    Invincibility
+}
+
+// Item box, reward is rolled by the server when it breaks
+pub(crate) struct KAFMonitor
+{
+    pub id: u8,
+    pub x: u16,
+    pub y: u16,
+    pub broken: bool,
+    pub timer: u16
+}
+
+impl Entity for KAFMonitor
+{
+    fn spawn(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        Some(self.state(*id))
+    }
+
+    fn tick(&mut self, server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        if !self.broken {
+            return None;
+        }
+
+        self.timer -= 1;
+        if self.timer > 0 {
+            return None;
+        }
+
+        self.broken = false;
+        server.multicast_real(&mut self.state(*id));
+        None
+    }
+
+    fn destroy(&mut self, _server: &mut Server, _game: &mut Game, _id: &u16) -> Option<Packet> 
+    {
+        None
+    }
+
+    fn id(&self) -> &str {
+        "kafmonitor"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl KAFMonitor
+{
+    pub fn open(&mut self, id: u16, by: u16) -> (Packet, MonitorReward)
+    {
+        let reward = match thread_rng().gen_range(0..100) {
+            0..=49 => MonitorReward::Rings,
+            50..=69 => MonitorReward::Shield,
+            70..=89 => MonitorReward::Speed,
+            _ => MonitorReward::Invincibility
+        };
+
+        self.broken = true;
+        self.timer = MONITOR_RESPAWN;
+
+        let mut packet = self.state(id);
+        packet.wu16(by);
+        packet.wu8(reward as u8);
+        (packet, reward)
+    }
+
+    fn state(&self, id: u16) -> Packet
+    {
+        let mut packet = Packet::new(PacketType::SERVER_KAFMONITOR_STATE);
+        packet.wu8(self.broken as u8);
+        packet.wu16(id);
+        packet.wu8(self.id);
+        packet.wu16(self.x);
+        packet.wu16(self.y);
+        packet
+    }
+}
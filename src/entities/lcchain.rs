@@ -0,0 +1,63 @@
+use rand::{thread_rng, Rng};
+
+use crate::{entity::Entity, states::game::Game, server::Server, packet::{Packet, PacketType}};
+
+// Swinging chain, hurts players while active
+pub(crate) struct LCChain
+{
+    pub id: u8,
+    pub active: bool,
+    pub timer: u16
+}
+
+impl Entity for LCChain
+{
+    fn spawn(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        // Don't let all chains swing at once
+        self.timer = thread_rng().gen_range(5..20) * 60;
+        Some(self.state(*id))
+    }
+
+    fn tick(&mut self, server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        self.timer -= 1;
+        if self.timer > 0 {
+            return None;
+        }
+
+        self.active = !self.active;
+        self.timer = if self.active { 3 * 60 } else { thread_rng().gen_range(8..15) * 60 };
+        server.multicast_real(&mut self.state(*id));
+        None
+    }
+
+    fn destroy(&mut self, _server: &mut Server, _game: &mut Game, _id: &u16) -> Option<Packet> 
+    {
+        None
+    }
+
+    fn id(&self) -> &str {
+        "lcchain"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl LCChain
+{
+    fn state(&self, id: u16) -> Packet
+    {
+        let mut packet = Packet::new(PacketType::SERVER_LCCHAIN_STATE);
+        packet.wu16(id);
+        packet.wu8(self.id);
+        packet.wu8(self.active as u8);
+        packet
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{entity::Entity, states::game::Game, server::Server, packet::{Packet, PacketType}};
+
+pub(crate) const EYE_MAX_CHARGE: u16 = 8 * 60; // Frames of use
+pub(crate) const EYE_MIN_CHARGE: u16 = 2 * 60; // Needed to start using the eye
+pub(crate) const EYE_COOLDOWN: u16 = 5 * 60;
+
+// Survivors can look through the eye until it runs out of charge
+pub(crate) struct LCEye
+{
+    pub id: u8,
+    pub used_by: Option<u16>,
+    pub charge: u16,
+    pub cooldown: u16,
+    pub sync_timer: u8
+}
+
+impl Entity for LCEye
+{
+    fn spawn(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        Some(self.state(*id))
+    }
+
+    fn tick(&mut self, server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        self.sync_timer = self.sync_timer.saturating_sub(1);
+
+        if self.used_by.is_some() {
+            self.charge = self.charge.saturating_sub(1);
+
+            // Ran out
+            if self.charge == 0 {
+                self.release();
+                server.multicast_real(&mut self.state(*id));
+                return None;
+            }
+        }
+        else if self.cooldown > 0 {
+            self.cooldown -= 1;
+            return None;
+        }
+        else if self.charge < EYE_MAX_CHARGE {
+            self.charge += 1;
+        }
+        else {
+            return None;
+        }
+
+        if self.sync_timer == 0 || self.charge == EYE_MAX_CHARGE {
+            self.sync_timer = 15;
+            return Some(self.state(*id));
+        }
+
+        None
+    }
+
+    fn destroy(&mut self, _server: &mut Server, _game: &mut Game, _id: &u16) -> Option<Packet> 
+    {
+        None
+    }
+
+    fn id(&self) -> &str {
+        "lceye"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl LCEye
+{
+    pub fn release(&mut self)
+    {
+        self.used_by = None;
+        self.cooldown = EYE_COOLDOWN;
+    }
+
+    pub fn state(&self, id: u16) -> Packet
+    {
+        let mut packet = Packet::new(PacketType::SERVER_LCEYE_STATE);
+        packet.wu16(id);
+        packet.wu8(self.id);
+        packet.wu8(self.used_by.is_some() as u8);
+        packet.wu16(self.used_by.unwrap_or(0));
+        packet.wu8((self.charge * 100 / EYE_MAX_CHARGE) as u8);
+        packet
+    }
+}
@@ -10,4 +10,49 @@ pub mod exclone;
 
 // Ravine Mist
 pub mod slug;
-pub mod shard;
\ No newline at end of file
+pub mod shard;
+
+// Limb City
+pub mod lceye;
+pub mod lcchain;
+
+// Nasty Paradise
+pub mod napball;
+pub mod napice;
+
+// Kind and Fair
+pub mod kafmonitor;
+
+// You Can't Run
+pub mod ycrsmoke;
+
+// Act 9
+pub mod act9wall;
+pub mod movingspike;
+
+// Pricefield
+pub mod pflift;
+
+// Volcano Valley
+pub mod vvlcolumn;
+pub mod vvvase;
+
+// Green Hill
+pub mod ghzthunder;
+
+// Torture Cave
+pub mod tcgom;
+
+// Desert Town
+pub mod dttailsdoll;
+pub mod dtball;
+pub mod dtass;
+
+// Haunting Dream
+pub mod hddoor;
+
+// Fart Zone
+pub mod fart;
+
+// Not Perfect
+pub mod npcontroller;
\ No newline at end of file
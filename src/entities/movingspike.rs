@@ -0,0 +1,73 @@
+use crate::{entity::Entity, states::game::Game, server::Server, packet::{Packet, PacketType}};
+
+// Goes back and forth between x and x + range, position only depends on the frame
+pub(crate) struct MovingSpike
+{
+    pub x: u16,
+    pub y: u16,
+    pub range: u16,
+    pub frame: u16
+}
+
+impl Entity for MovingSpike
+{
+    fn spawn(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        let mut packet = Packet::new(PacketType::SERVER_MOVINGSPIKE_STATE);
+        packet.wu8(0);
+        packet.wu16(*id);
+        packet.wu16(self.x);
+        packet.wu16(self.y);
+        packet.wu16(self.range);
+        packet.wu16(self.frame);
+        Some(packet)
+    }
+
+    fn tick(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        self.frame = (self.frame + 1) % (self.range * 2);
+
+        // Clients move them on their own, just correct them when they turn around
+        if self.frame != 0 && self.frame != self.range {
+            return None;
+        }
+
+        let mut packet = Packet::new(PacketType::SERVER_MOVINGSPIKE_STATE);
+        packet.wu8(1);
+        packet.wu16(*id);
+        packet.wu16(self.position());
+        packet.wu16(self.frame);
+        Some(packet)
+    }
+
+    fn destroy(&mut self, _server: &mut Server, _game: &mut Game, _id: &u16) -> Option<Packet> 
+    {
+        None
+    }
+
+    fn id(&self) -> &str {
+        "movingspike"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl MovingSpike
+{
+    // One pixel per frame
+    pub fn position(&self) -> u16
+    {
+        if self.frame < self.range {
+            self.x + self.frame
+        }
+        else {
+            self.x + self.range * 2 - self.frame
+        }
+    }
+}
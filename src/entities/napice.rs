@@ -0,0 +1,81 @@
+use crate::{entity::Entity, states::game::Game, server::Server, packet::{Packet, PacketType}};
+
+pub(crate) const ICE_TIME: u16 = 5 * 60;
+pub(crate) const ICE_COOLDOWN: u16 = 10 * 60;
+
+// Ice block that freezes the floor around it when triggered
+pub(crate) struct NAPIce
+{
+    pub id: u8,
+    pub active: bool,
+    pub timer: u16, // Time left while active, cooldown otherwise
+    pub activated_by: u16
+}
+
+impl Entity for NAPIce
+{
+    fn spawn(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        Some(self.state(*id))
+    }
+
+    fn tick(&mut self, server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        if self.timer == 0 {
+            return None;
+        }
+
+        self.timer -= 1;
+        if self.timer > 0 || !self.active {
+            return None;
+        }
+
+        self.active = false;
+        self.timer = ICE_COOLDOWN;
+        server.multicast_real(&mut self.state(*id));
+        None
+    }
+
+    fn destroy(&mut self, _server: &mut Server, _game: &mut Game, _id: &u16) -> Option<Packet> 
+    {
+        None
+    }
+
+    fn id(&self) -> &str {
+        "napice"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl NAPIce
+{
+    // Inactive ice can be triggered once it's cooldown is over
+    pub fn ready(&self) -> bool
+    {
+        !self.active && self.timer == 0
+    }
+
+    pub fn activate(&mut self, by: u16)
+    {
+        self.active = true;
+        self.timer = ICE_TIME;
+        self.activated_by = by;
+    }
+
+    pub fn state(&self, id: u16) -> Packet
+    {
+        let mut packet = Packet::new(PacketType::SERVER_NAPICE_STATE);
+        packet.wu16(id);
+        packet.wu8(self.id);
+        packet.wu8(self.active as u8);
+        packet.wu16(self.activated_by);
+        packet
+    }
+}
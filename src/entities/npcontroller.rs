@@ -0,0 +1,70 @@
+use crate::{entity::Entity, states::game::Game, server::Server, packet::{Packet, PacketType}};
+
+pub(crate) const PHASE_COUNT: u8 = 4;
+pub(crate) const PHASE_TIME: u16 = 15 * 60;
+
+// Switches the level between it's variants for everyone at once
+pub(crate) struct NPController
+{
+    pub phase: u8,
+    pub timer: u16,
+    pub sync_timer: u8
+}
+
+impl Entity for NPController
+{
+    fn spawn(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        self.timer = PHASE_TIME;
+        Some(self.state(*id))
+    }
+
+    fn tick(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        self.timer -= 1;
+        self.sync_timer = self.sync_timer.saturating_sub(1);
+
+        if self.timer == 0 {
+            self.phase = (self.phase + 1) % PHASE_COUNT;
+            self.timer = PHASE_TIME;
+            return Some(self.state(*id));
+        }
+
+        // Countdown sync once a second
+        if self.sync_timer == 0 {
+            self.sync_timer = 60;
+            return Some(self.state(*id));
+        }
+
+        None
+    }
+
+    fn destroy(&mut self, _server: &mut Server, _game: &mut Game, _id: &u16) -> Option<Packet> 
+    {
+        None
+    }
+
+    fn id(&self) -> &str {
+        "npcontroller"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl NPController
+{
+    fn state(&self, id: u16) -> Packet
+    {
+        let mut packet = Packet::new(PacketType::SERVER_NPCONTROLLER_STATE);
+        packet.wu16(id);
+        packet.wu8(self.phase);
+        packet.wu8((self.timer / 60) as u8);
+        packet
+    }
+}
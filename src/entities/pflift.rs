@@ -0,0 +1,132 @@
+use crate::{entity::Entity, states::game::Game, server::Server, packet::{Packet, PacketType}};
+
+pub(crate) const LIFT_SPEED: u16 = 2;
+pub(crate) const LIFT_WAIT: u16 = 3 * 60; // At the top
+pub(crate) const LIFT_COOLDOWN: u16 = 2 * 60; // At the bottom
+
+#[derive(Copy, Clone, PartialEq)]
+pub(crate) enum LiftState
+{
+    Idle,
+    Up,
+    Top,
+    Down
+}
+
+// Goes up when the player standing on it asks to
+pub(crate) struct PFLift
+{
+    pub id: u8,
+    pub x: u16,
+    pub y: u16,
+    pub bottom: u16,
+    pub top: u16,
+    pub state: LiftState,
+    pub timer: u16,
+    pub rider: u16
+}
+
+impl Entity for PFLift
+{
+    fn spawn(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        Some(self.state(*id))
+    }
+
+    fn tick(&mut self, server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        self.timer = self.timer.saturating_sub(1);
+
+        match self.state {
+            LiftState::Idle | LiftState::Top if self.timer > 0 => None,
+
+            LiftState::Idle => None,
+
+            LiftState::Top => {
+                self.state = LiftState::Down;
+                server.multicast_real(&mut self.state(*id));
+                None
+            },
+
+            LiftState::Up => {
+                self.y = self.y.saturating_sub(LIFT_SPEED).max(self.top);
+                if self.y == self.top {
+                    self.state = LiftState::Top;
+                    self.timer = LIFT_WAIT;
+                    server.multicast_real(&mut self.state(*id));
+                    return None;
+                }
+
+                self.sync(*id)
+            },
+
+            LiftState::Down => {
+                self.y = (self.y + LIFT_SPEED).min(self.bottom);
+                if self.y == self.bottom {
+                    self.state = LiftState::Idle;
+                    self.timer = LIFT_COOLDOWN;
+                    self.rider = 0;
+                    server.multicast_real(&mut self.state(*id));
+                    return None;
+                }
+
+                self.sync(*id)
+            }
+        }
+    }
+
+    fn destroy(&mut self, _server: &mut Server, _game: &mut Game, _id: &u16) -> Option<Packet> 
+    {
+        None
+    }
+
+    fn id(&self) -> &str {
+        "pflift"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl PFLift
+{
+    pub fn ready(&self) -> bool
+    {
+        self.state == LiftState::Idle && self.timer == 0
+    }
+
+    // Player's position is checked by the map
+    pub fn activate(&mut self, rider: u16)
+    {
+        self.state = LiftState::Up;
+        self.rider = rider;
+    }
+
+    pub fn state(&self, id: u16) -> Packet
+    {
+        let mut packet = Packet::new(PacketType::SERVER_PFLIFT_STATE);
+        packet.wu16(id);
+        packet.wu8(self.id);
+        packet.wu8(self.state as u8);
+        packet.wu16(self.y);
+        packet.wu16(self.rider);
+        packet
+    }
+
+    // Position while moving, timer isn't used for anything else meanwhile
+    // State changes go over TCP, these can get lost
+    fn sync(&mut self, id: u16) -> Option<Packet>
+    {
+        if self.timer > 0 {
+            return None;
+        }
+
+        self.timer = 8;
+        Some(self.state(id))
+    }
+}
@@ -1,17 +1,18 @@
 use crate::{entity::Entity, states::game::Game, server::Server, packet::{Packet, PacketType}};
 
+// SERVER_RMZSHARD_STATE: 0 placed, 1 dropped, 2 removed, 3 player's count
 pub(crate) struct Shard
 {
     pub x: u16,
     pub y: u16,
-    pub spawned: bool
+    pub spawned: bool // Dropped by a player
 }
 
 impl Entity for Shard
 {
     fn spawn(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
     {        
-        let mut packet = Packet::new(PacketType::SERVER_RING_STATE);
+        let mut packet = Packet::new(PacketType::SERVER_RMZSHARD_STATE);
         packet.wu8(self.spawned as u8);
         packet.wu16(*id);
         packet.wu16(self.x);
@@ -24,9 +25,12 @@ impl Entity for Shard
         None
     }
 
-    fn destroy(&mut self, _server: &mut Server, _game: &mut Game, _id: &u16) -> Option<Packet> 
+    fn destroy(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
     {
-        None
+        let mut packet = Packet::new(PacketType::SERVER_RMZSHARD_STATE);
+        packet.wu8(2);
+        packet.wu16(*id);
+        Some(packet)
     }
 
     fn id(&self) -> &str {
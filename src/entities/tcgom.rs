@@ -0,0 +1,89 @@
+use rand::{thread_rng, Rng};
+
+use crate::{entity::Entity, states::game::Game, server::Server, packet::{Packet, PacketType}};
+
+pub(crate) const GOM_WAKING: u16 = 3 * 60;
+pub(crate) const GOM_WATCHING: u16 = 6 * 60;
+
+#[derive(Copy, Clone, PartialEq)]
+pub(crate) enum GomState
+{
+    Sleeping,
+    Waking, // Warning for players
+    Watching
+}
+
+// Torture Cave's creature, wakes up from time to time and watches the cave
+pub(crate) struct TCGom
+{
+    pub state: GomState,
+    pub timer: u16
+}
+
+impl Entity for TCGom
+{
+    fn spawn(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        self.state = GomState::Sleeping;
+        self.timer = thread_rng().gen_range(15..25) * 60;
+        Some(self.packet(*id))
+    }
+
+    fn tick(&mut self, server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        self.timer -= 1;
+        if self.timer > 0 {
+            return None;
+        }
+
+        match self.state {
+            GomState::Sleeping => {
+                self.state = GomState::Waking;
+                self.timer = GOM_WAKING;
+            },
+
+            GomState::Waking => {
+                self.state = GomState::Watching;
+                self.timer = GOM_WATCHING;
+            },
+
+            GomState::Watching => {
+                self.state = GomState::Sleeping;
+                self.timer = thread_rng().gen_range(20..35) * 60;
+            }
+        }
+
+        server.multicast_real(&mut self.packet(*id));
+        None
+    }
+
+    fn destroy(&mut self, _server: &mut Server, _game: &mut Game, _id: &u16) -> Option<Packet> 
+    {
+        None
+    }
+
+    fn id(&self) -> &str {
+        "tcgom"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl TCGom
+{
+    // Clients show how long the phase lasts
+    fn packet(&self, id: u16) -> Packet
+    {
+        let mut packet = Packet::new(PacketType::SERVER_TCGOM_STATE);
+        packet.wu16(id);
+        packet.wu8(self.state as u8);
+        packet.wu8((self.timer / 60) as u8);
+        packet
+    }
+}
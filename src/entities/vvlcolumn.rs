@@ -0,0 +1,88 @@
+use rand::{thread_rng, Rng};
+
+use crate::{entity::Entity, states::game::Game, server::Server, packet::{Packet, PacketType}};
+
+pub(crate) const COLUMN_WARNING: u16 = 2 * 60;
+pub(crate) const COLUMN_ACTIVE: u16 = 3 * 60;
+
+#[derive(Copy, Clone, PartialEq)]
+pub(crate) enum ColumnState
+{
+    Idle,
+    Warning, // Bubbling before it erupts
+    Active
+}
+
+// Lava column erupting from the floor
+pub(crate) struct VVLColumn
+{
+    pub id: u8,
+    pub state: ColumnState,
+    pub timer: u16
+}
+
+impl Entity for VVLColumn
+{
+    fn spawn(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        self.timer = thread_rng().gen_range(4..12) * 60;
+        Some(self.packet(*id))
+    }
+
+    fn tick(&mut self, server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        self.timer -= 1;
+        if self.timer > 0 {
+            return None;
+        }
+
+        match self.state {
+            ColumnState::Idle => {
+                self.state = ColumnState::Warning;
+                self.timer = COLUMN_WARNING;
+            },
+
+            ColumnState::Warning => {
+                self.state = ColumnState::Active;
+                self.timer = COLUMN_ACTIVE;
+            },
+
+            ColumnState::Active => {
+                self.state = ColumnState::Idle;
+                self.timer = thread_rng().gen_range(6..14) * 60;
+            }
+        }
+
+        server.multicast_real(&mut self.packet(*id));
+        None
+    }
+
+    fn destroy(&mut self, _server: &mut Server, _game: &mut Game, _id: &u16) -> Option<Packet> 
+    {
+        None
+    }
+
+    fn id(&self) -> &str {
+        "vvlcolumn"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl VVLColumn
+{
+    fn packet(&self, id: u16) -> Packet
+    {
+        let mut packet = Packet::new(PacketType::SERVER_VVLCOLUMN_STATE);
+        packet.wu16(id);
+        packet.wu8(self.id);
+        packet.wu8(self.state as u8);
+        packet
+    }
+}
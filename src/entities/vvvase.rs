@@ -0,0 +1,84 @@
+use rand::{thread_rng, Rng};
+
+use crate::{entity::Entity, states::game::Game, server::Server, packet::{Packet, PacketType}};
+
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum VaseContent
+{
+    Nothing,
+    Ring,
+    Rings,
+    RedRing
+}
+
+// Breaks only once per round
+pub(crate) struct VVVase
+{
+    pub id: u8,
+    pub x: u16,
+    pub y: u16,
+    pub broken: bool
+}
+
+impl Entity for VVVase
+{
+    fn spawn(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        let mut packet = Packet::new(PacketType::SERVER_VVVASE_STATE);
+        packet.wu8(0);
+        packet.wu16(*id);
+        packet.wu8(self.id);
+        packet.wu16(self.x);
+        packet.wu16(self.y);
+        Some(packet)
+    }
+
+    fn tick(&mut self, _server: &mut Server, _game: &mut Game, _id: &u16) -> Option<Packet> 
+    {
+        None
+    }
+
+    fn destroy(&mut self, _server: &mut Server, _game: &mut Game, _id: &u16) -> Option<Packet> 
+    {
+        None
+    }
+
+    fn id(&self) -> &str {
+        "vvvase"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl VVVase
+{
+    // Returns None if someone already broke it
+    pub fn break_by(&mut self, id: u16, by: u16) -> Option<(Packet, VaseContent)>
+    {
+        if self.broken {
+            return None;
+        }
+
+        self.broken = true;
+
+        let content = match thread_rng().gen_range(0..100) {
+            0..=39 => VaseContent::Nothing,
+            40..=74 => VaseContent::Ring,
+            75..=94 => VaseContent::Rings,
+            _ => VaseContent::RedRing
+        };
+
+        let mut packet = Packet::new(PacketType::SERVER_VVVASE_STATE);
+        packet.wu8(1);
+        packet.wu16(id);
+        packet.wu16(by);
+        packet.wu8(content as u8);
+        Some((packet, content))
+    }
+}
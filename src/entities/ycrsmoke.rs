@@ -0,0 +1,73 @@
+use crate::{entity::Entity, states::game::Game, server::Server, packet::{Packet, PacketType}};
+
+pub(crate) const SMOKE_WARNING: u16 = 3 * 60;
+pub(crate) const SMOKE_ACTIVE: u16 = 8 * 60;
+
+// Poison smoke, players get a warning before it starts hurting
+pub(crate) struct YCRSmoke
+{
+    pub x: u16,
+    pub y: u16,
+    pub active: bool,
+    pub timer: u16
+}
+
+impl Entity for YCRSmoke
+{
+    fn spawn(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        self.active = false;
+        self.timer = SMOKE_WARNING;
+
+        let mut packet = Packet::new(PacketType::SERVER_YCRSMOKE_READY);
+        packet.wu16(*id);
+        packet.wu16(self.x);
+        packet.wu16(self.y);
+        packet.wu8((SMOKE_WARNING / 60) as u8);
+        Some(packet)
+    }
+
+    fn tick(&mut self, server: &mut Server, game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        self.timer -= 1;
+        if self.timer > 0 {
+            return None;
+        }
+
+        if self.active {
+            game.queue_destroy(id);
+            return None;
+        }
+
+        self.active = true;
+        self.timer = SMOKE_ACTIVE;
+
+        let mut packet = Packet::new(PacketType::SERVER_YCRSMOKE_STATE);
+        packet.wu8(0);
+        packet.wu16(*id);
+        packet.wu16(self.x);
+        packet.wu16(self.y);
+        server.multicast_real(&mut packet);
+        None
+    }
+
+    fn destroy(&mut self, _server: &mut Server, _game: &mut Game, id: &u16) -> Option<Packet> 
+    {
+        let mut packet = Packet::new(PacketType::SERVER_YCRSMOKE_STATE);
+        packet.wu8(1);
+        packet.wu16(*id);
+        Some(packet)
+    }
+
+    fn id(&self) -> &str {
+        "ycrsmoke"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
@@ -0,0 +1,89 @@
+use std::net::UdpSocket;
+use std::thread;
+use std::time::Duration;
+
+use log::{info, warn, debug};
+use serde::Serialize;
+
+use crate::{config, query::{self, ServerInfo}, states::lobby::BUILD_VER};
+
+// Datagram sent to the master server (see src/bin/master.rs)
+#[derive(Serialize)]
+struct Heartbeat
+{
+    action: String,
+    address: String,
+    port: u16,
+    build: u16,
+    servers: Vec<ServerInfo>
+}
+
+pub(crate) fn start()
+{
+    let _ = thread::Builder::new().name("heartbeat".to_string()).spawn(move || {
+        let mut registered = false;
+
+        // Master settings can be changed while running
+        loop {
+            let config = config::get();
+
+            if config.master.enabled {
+                if !registered {
+                    info!("Registering at master server {}", config.master.address);
+                }
+
+                send("register");
+            }
+            else if registered {
+                send("unregister");
+            }
+
+            registered = config.master.enabled;
+            thread::sleep(Duration::from_secs(config.master.interval.max(1) as u64));
+        }
+    });
+}
+
+// Called before shutting down, so we disappear from the list right away
+pub(crate) fn unregister()
+{
+    if !config::get().master.enabled {
+        return;
+    }
+
+    send("unregister");
+    info!("Unregistered from master server");
+}
+
+fn send(action: &str)
+{
+    let config = config::get();
+    let heartbeat = Heartbeat {
+        action: action.to_string(),
+        address: config.master.public_address.clone(),
+        port: config.server.tcp_port,
+        build: BUILD_VER,
+        servers: if action == "register" { query::snapshot() } else { Vec::new() }
+    };
+
+    let value = match toml::to_string(&heartbeat) {
+        Ok(res) => res,
+        Err(err) => {
+            warn!("Failed to serialize heartbeat: {}", err);
+            return;
+        }
+    };
+
+    let socket = match UdpSocket::bind("0.0.0.0:0") {
+        Ok(res) => res,
+        Err(err) => {
+            warn!("Failed to open heartbeat socket: {}", err);
+            return;
+        }
+    };
+
+    match socket.send_to(value.as_bytes(), config.master.address.as_str()) {
+        Ok(_) => debug!("Sent {} to master server", action),
+        Err(err) => warn!("Failed to reach master server {}: {}", config.master.address, err)
+    }
+}
@@ -1,12 +1,18 @@
-use std::{net::TcpListener, sync::{Mutex, Arc}, ops::AddAssign};
+use std::net::TcpListener;
+use std::process;
 
+use args::ARGS;
 use chrono::Utc;
-use config::CONFIG;
 use log::{LevelFilter, error, warn, info};
 use log4rs::{append::{console::ConsoleAppender, file::FileAppender}, encode::pattern::PatternEncoder, Config, config::{Appender, Root}};
 use server::Server;
 
+mod args;
 mod config;
+mod balancer;
+mod console;
+mod query;
+mod heartbeat;
 mod timer;
 mod server;
 mod packet;
@@ -19,63 +25,42 @@ mod states;
 
 fn init_logger()
 {
-    let mut level = LevelFilter::Info;
-
-    if CONFIG.debug {
-        level = LevelFilter::Debug;
-    }
-
     let console = ConsoleAppender::builder()
     .encoder(Box::new(PatternEncoder::new("[{h({l})} {T} {d(%Y-%m-%d %H:%M:%S)}] {m} {n}")))
     .build();
 
     let logfile = FileAppender::builder()
     .encoder(Box::new(PatternEncoder::new("[{h({l})} {T} {d(%Y-%m-%d %H:%M:%S)}] {m} {n}")))
-    .build(format!("logs/{}.log", Utc::now().format("%Y-%m-%d %H-%M-%S")))
+    .build(format!("{}/{}.log", ARGS.log_dir, Utc::now().format("%Y-%m-%d %H-%M-%S")))
     .unwrap();
 
     let config = Config::builder()
     .appender(Appender::builder().build("console", Box::new(console)))
     .appender(Appender::builder().build("logfile", Box::new(logfile)))
-    .build(Root::builder().appender("console").appender("logfile").build(level))
+    .build(Root::builder().appender("console").appender("logfile").build(LevelFilter::Trace))
     .unwrap();
     
+    // Actual level is set here, so it can be changed on reload
     log4rs::init_config(config).unwrap();
+    log::set_max_level(config::get().log_level());
 }
 
-fn find_free_server(servers: &mut Vec<Arc<Mutex<Server>>>, port: &mut u16) -> Arc<Mutex<Server>>
+fn main()
 {
-    if CONFIG.server.grow {
-        for server in servers.iter() {
-            if server.lock().unwrap().peers.read().unwrap().len() < 7 {
-                return server.clone();
-            }
-        }
-        
-        if servers.len() >= CONFIG.server.grow_limit as usize {
-            warn!("Couldn't allocate new server: FULL ({}/{})!", servers.len(), CONFIG.server.grow_limit);
-            return servers.last().unwrap().clone();
-        }
-
-        info!("Allocating new sub-server... ({}/{})", servers.len() + 1, CONFIG.server.grow_limit);
-        port.add_assign(1);
-
-        let server = Server::start(*port, format!("server{}", servers.len()));
-        servers.push(server.clone());
-        return server;
+    if ARGS.print_config {
+        print!("{}", config::dump());
+        return;
     }
-    else {
-        return servers.get(0).unwrap().clone();
+
+    // Before the logger, so no log file is created
+    if ARGS.migrate_config {
+        process::exit(if config::migrate() { 0 } else { 1 });
     }
-}
 
-fn main()
-{
     init_logger();
 
-    let mut servers: Vec<Arc<Mutex<Server>>> = Vec::new();
-    let mut port = CONFIG.server.udp_port;
-    let listner = match TcpListener::bind(format!("0.0.0.0:{}", CONFIG.server.tcp_port))
+    let port = config::get().server.tcp_port;
+    let listner = match TcpListener::bind(format!("0.0.0.0:{}", port))
     {
         Ok(res) => res,
         Err(err) => {
@@ -84,7 +69,18 @@ fn main()
         }
     };
 
-    info!("Listening for connections on {}/tcp", CONFIG.server.tcp_port);
+    info!("Listening for connections on {}/tcp", port);
+    if balancer::allocate(None).is_none() {
+        error!("Failed to start the first sub-server");
+        return;
+    }
+
+    balancer::start();
+    console::start();
+    query::start();
+    heartbeat::start();
+    config::watch();
+
     for stream in listner.incoming()
     {
         // If failed to open a stream, ignore
@@ -96,7 +92,7 @@ fn main()
             }
         };
 
-        let server = find_free_server(&mut servers, &mut port);
+        let server = balancer::find_free_server();
         Server::peer_redirect(server, stream);
     }
 }
\ No newline at end of file
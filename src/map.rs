@@ -1,15 +1,17 @@
 use std::sync::{Mutex, Arc};
 
-use crate::{server::{Server, real_peers, Peer}, packet::Packet, states::game::Game};
+use crate::{config, server::{Server, real_peers, Peer}, packet::Packet, states::game::Game};
 
 pub(crate) trait Map: Send + Sync
 {
     fn timer(&self, server: &Server) -> f32 {
-        (180.0 + 100.0 * self.player_time_multiplier(server)) * 60.0
+        let rules = config::get().rules(&server.name, Some(self.name()));
+        (rules.round_time as f32 + rules.round_time_bonus as f32 * self.player_time_multiplier(server)) * 60.0
     }
 
     fn player_time_multiplier(&self, server: &Server) -> f32 {
-        (real_peers!(server).count() as f32) / 7.0
+        let rules = config::get().rules(&server.name, Some(self.name()));
+        (real_peers!(server).count() as f32) / rules.max_players as f32
     }
 
     fn ring_count(&self) -> usize {
@@ -33,6 +35,7 @@ pub(crate) trait Map: Send + Sync
     }
  
     fn init(&mut self, _server: &mut Server, _game: &mut Game) {}
+    fn peer_ready(&mut self, _server: &mut Server, _game: &mut Game, _peer: Arc<Mutex<Peer>>) {} // After init, for every UDP ready player
     fn tick(&mut self, _server: &mut Server, _game: &mut Game) {}
     fn got_tcp_packet(&mut self, _server: &mut Server, _game: &mut Game, _peer: Arc<Mutex<Peer>>, _packet: &mut Packet) -> Result<(), &'static str> { Ok(()) }
 
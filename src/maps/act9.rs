@@ -0,0 +1,61 @@
+use crate::{map::Map, states::game::Game, server::Server, entities::{act9wall::{Act9Wall, WallState}, movingspike::MovingSpike}};
+
+// Round time left when each wall closes
+const WALLS: [u16; 3] = [150 * 60, 100 * 60, 60 * 60];
+
+// x, y, range
+const SPIKES: [(u16, u16, u16); 5] = [
+    (620, 1432, 180),
+    (1340, 1432, 240),
+    (2010, 916, 160),
+    (2750, 1432, 200),
+    (1720, 2204, 300)
+];
+
+pub(crate) struct Act9
+{
+
+}
+
+impl Map for Act9
+{
+    fn init(&mut self, server: &mut Server, game: &mut Game) 
+    {
+        for (id, close_at) in WALLS.iter().enumerate() {
+            game.spawn(server, Box::new(Act9Wall {
+                id: id as u8,
+                close_at: *close_at,
+                state: WallState::Open
+            }));
+        }
+
+        // Same start for every round, so spikes are in sync with the level
+        for spike in SPIKES.iter() {
+            game.spawn(server, Box::new(MovingSpike {
+                x: spike.0,
+                y: spike.1,
+                range: spike.2,
+                frame: 0
+            }));
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Act 9"
+    }
+
+    fn index(&self) -> usize {
+        8
+    }
+
+    fn ring_count(&self) -> usize {
+        22
+    }
+}
+
+impl Act9
+{
+    pub fn new() -> Act9 {
+        Act9 { }
+    }
+}
@@ -0,0 +1,141 @@
+use std::sync::{Arc, Mutex};
+
+use log::info;
+use rand::{thread_rng, Rng};
+
+use crate::{map::Map, states::game::{Game, find_entities, find_entities_mut}, server::{Server, Peer, assert_or_disconnect}, entities::{dttailsdoll::DTTailsDoll, dtball::DTBall, dtass::DTAss}, packet::{Packet, PacketType}};
+
+const DOLL_SPAWN: (f32, f32) = (1680.0, 420.0);
+const DOLL_STUN: u16 = 6 * 60;
+
+const BELLS: [(u16, u16); 3] = [
+    (520, 1236),
+    (1720, 980),
+    (2930, 1236)
+];
+
+const BELL_RANGE: f32 = 96.0;
+
+// x, y, direction
+const BALL_SPAWNS: [(f32, f32, i8); 2] = [
+    (64.0, 1262.0, 1),
+    (3380.0, 1262.0, -1)
+];
+
+pub(crate) struct DesertTown
+{
+    ball_timer: u16
+}
+
+impl Map for DesertTown
+{
+    fn init(&mut self, server: &mut Server, game: &mut Game) 
+    {
+        self.ball_timer = thread_rng().gen_range(8..16) * 60;
+
+        game.spawn(server, Box::new(DTTailsDoll {
+            x: DOLL_SPAWN.0,
+            y: DOLL_SPAWN.1,
+            target: 0,
+            stun: 0,
+            sync_timer: 0
+        }));
+
+        for (id, point) in BELLS.iter().enumerate() {
+            game.spawn(server, Box::new(DTAss {
+                id: id as u8,
+                x: point.0,
+                y: point.1,
+                cooldown: 0
+            }));
+        }
+    }
+
+    fn tick(&mut self, server: &mut Server, game: &mut Game) 
+    {
+        self.ball_timer -= 1;
+        if self.ball_timer > 0 {
+            return;
+        }
+
+        self.ball_timer = thread_rng().gen_range(12..24) * 60;
+        if find_entities!(game.entities.lock().unwrap(), "dtball").count() > 0 {
+            return;
+        }
+
+        let point = BALL_SPAWNS[thread_rng().gen_range(0..BALL_SPAWNS.len())];
+        game.spawn(server, Box::new(DTBall {
+            x: point.0,
+            y: point.1,
+            dir: point.2,
+            timer: 14 * 60
+        }));
+    }
+
+    fn got_tcp_packet(&mut self, server: &mut Server, game: &mut Game, peer: Arc<Mutex<Peer>>, packet: &mut Packet) -> Result<(), &'static str> 
+    {
+        let _passtrough = packet.ru8()? != 0; //TODO: get rid of
+        let tp = packet.rpk()?;
+        let id = peer.lock().unwrap().id();
+
+        if let PacketType::CLIENT_DTASS_ACTIVATE = tp {
+            let eid = packet.ru16()?;
+
+            {
+                let mut peer = peer.lock().unwrap();
+                let player = peer.player.as_ref().unwrap();
+                let can_use = !player.exe && !player.dead && player.revival_times < 2;
+                assert_or_disconnect!(can_use, &mut peer);
+            }
+
+            let entities = game.entities.clone();
+            let mut entities = entities.lock().unwrap();
+            let bell = match entities.get_mut(&eid) {
+                Some(res) if res.id() == "dtass" => res.as_any_mut().downcast_mut::<DTAss>().unwrap(),
+                _ => return Ok(())
+            };
+
+            if bell.cooldown > 0 {
+                return Ok(());
+            }
+
+            if !game.player_near(id, bell.x as f32, bell.y as f32, BELL_RANGE) {
+                return Ok(());
+            }
+
+            server.multicast_real(&mut bell.activate(eid, id));
+
+            for doll in find_entities_mut!(entities, "dttailsdoll") {
+                let eid = *doll.0;
+                let doll = doll.1.as_any_mut().downcast_mut::<DTTailsDoll>().unwrap();
+                doll.stun = DOLL_STUN;
+                server.multicast_real(&mut doll.state(1, eid));
+            }
+
+            info!("{} (ID {}) stunned the doll", peer.lock().unwrap().nickname, id);
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "Desert Town"
+    }
+
+    fn index(&self) -> usize {
+        3
+    }
+
+    fn ring_count(&self) -> usize {
+        25
+    }
+}
+
+impl DesertTown
+{
+    pub fn new() -> DesertTown {
+        DesertTown { 
+            ball_timer: 0
+        }
+    }
+}
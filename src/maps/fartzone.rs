@@ -0,0 +1,94 @@
+use std::sync::{Arc, Mutex};
+
+use log::debug;
+
+use crate::{map::Map, states::game::Game, server::{Server, Peer, assert_or_disconnect}, entities::fart::Fart, packet::{Packet, PacketType}};
+
+const FART_SPAWNS: [(u16, u16); 6] = [
+    (420, 716),
+    (1138, 460),
+    (1876, 972),
+    (2544, 716),
+    (860, 1484),
+    (2210, 1740)
+];
+
+const FART_RANGE: f32 = 128.0;
+
+pub(crate) struct FartZone
+{
+
+}
+
+impl Map for FartZone
+{
+    fn init(&mut self, server: &mut Server, game: &mut Game) 
+    {
+        for (id, point) in FART_SPAWNS.iter().enumerate() {
+            game.spawn(server, Box::new(Fart {
+                id: id as u8,
+                x: point.0,
+                y: point.1,
+                active: 0,
+                cooldown: 0
+            }));
+        }
+    }
+
+    fn got_tcp_packet(&mut self, server: &mut Server, game: &mut Game, peer: Arc<Mutex<Peer>>, packet: &mut Packet) -> Result<(), &'static str> 
+    {
+        let _passtrough = packet.ru8()? != 0; //TODO: get rid of
+        let tp = packet.rpk()?;
+        let id = peer.lock().unwrap().id();
+
+        if let PacketType::CLIENT_FART_PUSH = tp {
+            let eid = packet.ru16()?;
+
+            {
+                let mut peer = peer.lock().unwrap();
+                let dead = peer.player.as_ref().unwrap().dead;
+                assert_or_disconnect!(!dead, &mut peer);
+            }
+
+            let entities = game.entities.clone();
+            let mut entities = entities.lock().unwrap();
+            let fart = match entities.get_mut(&eid) {
+                Some(res) if res.id() == "fart" => res.as_any_mut().downcast_mut::<Fart>().unwrap(),
+                _ => return Ok(())
+            };
+
+            if fart.cooldown > 0 {
+                return Ok(());
+            }
+
+            // Has to be next to the vent
+            if !game.player_near(id, fart.x as f32, fart.y as f32, FART_RANGE) {
+                debug!("ID {} is too far from vent {}", id, fart.id);
+                return Ok(());
+            }
+
+            server.multicast_real(&mut fart.push(eid, id));
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "Fart Zone"
+    }
+
+    fn index(&self) -> usize {
+        18
+    }
+
+    fn ring_count(&self) -> usize {
+        20
+    }
+}
+
+impl FartZone
+{
+    pub fn new() -> FartZone {
+        FartZone { }
+    }
+}
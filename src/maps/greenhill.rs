@@ -0,0 +1,77 @@
+use rand::{thread_rng, Rng};
+
+use crate::{map::Map, states::game::Game, server::{Server, real_peers}, entities::ghzthunder::GHZThunder};
+
+const MAP_WIDTH: u16 = 3400;
+const MAP_HEIGHT: u16 = 1500;
+const THUNDER_SPREAD: f32 = 200.0;
+
+pub(crate) struct GreenHill
+{
+    thunder_timer: u16
+}
+
+impl Map for GreenHill
+{
+    fn init(&mut self, _server: &mut Server, _game: &mut Game) 
+    {
+        self.thunder_timer = thread_rng().gen_range(10..20) * 60;
+    }
+
+    fn tick(&mut self, server: &mut Server, game: &mut Game) 
+    {
+        self.thunder_timer -= 1;
+        if self.thunder_timer > 0 {
+            return;
+        }
+
+        self.thunder_timer = thread_rng().gen_range(12..25) * 60;
+
+        // Strike somewhere near a living player, or anywhere if we don't know where they are
+        let alive: Vec<u16> = real_peers!(server).filter_map(|x| {
+            let peer = x.lock().unwrap();
+            match &peer.player {
+                Some(player) if !player.dead && !player.escaped => Some(peer.id()),
+                _ => None
+            }
+        }).collect();
+
+        let target = alive.get(thread_rng().gen_range(0..alive.len().max(1))).and_then(|x| game.players_pos.get(x)).copied();
+        let (x, y) = match target {
+            Some(pos) if pos != (0.0, 0.0) => {
+                let x = pos.0 + thread_rng().gen_range(-THUNDER_SPREAD..THUNDER_SPREAD);
+                (x.clamp(0.0, MAP_WIDTH as f32) as u16, pos.1 as u16)
+            },
+
+            _ => (thread_rng().gen_range(0..MAP_WIDTH), thread_rng().gen_range(0..MAP_HEIGHT))
+        };
+
+        game.spawn(server, Box::new(GHZThunder {
+            x,
+            y,
+            struck: false,
+            timer: 0
+        }));
+    }
+
+    fn name(&self) -> &str {
+        "Green Hill"
+    }
+
+    fn index(&self) -> usize {
+        12
+    }
+
+    fn ring_count(&self) -> usize {
+        27
+    }
+}
+
+impl GreenHill
+{
+    pub fn new() -> GreenHill {
+        GreenHill { 
+            thunder_timer: 0
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use std::sync::{Arc, Mutex};
+
+use log::debug;
+
+use crate::{map::Map, states::game::{Game, find_entities}, server::{Server, Peer, assert_or_disconnect}, entities::hddoor::HDDoor, packet::{Packet, PacketType}};
+
+const DOOR_COUNT: u8 = 9;
+
+pub(crate) struct HauntingDream
+{
+
+}
+
+impl Map for HauntingDream
+{
+    fn init(&mut self, server: &mut Server, game: &mut Game) 
+    {
+        for id in 0..DOOR_COUNT {
+            game.spawn_quiet(server, Box::new(HDDoor {
+                id,
+                open: id % 2 == 0,
+                cooldown: 0
+            }));
+        }
+    }
+
+    // Every door, so the player doesn't depend on the order of earlier toggles
+    fn peer_ready(&mut self, _server: &mut Server, game: &mut Game, peer: Arc<Mutex<Peer>>) 
+    {
+        let mut peer = peer.lock().unwrap();
+        for door in find_entities!(game.entities.lock().unwrap(), "hddoor") {
+            let state = door.1.as_any().downcast_ref::<HDDoor>().unwrap();
+            peer.send(&mut state.state(*door.0));
+        }
+    }
+
+    fn got_tcp_packet(&mut self, server: &mut Server, game: &mut Game, peer: Arc<Mutex<Peer>>, packet: &mut Packet) -> Result<(), &'static str> 
+    {
+        let _passtrough = packet.ru8()? != 0; //TODO: get rid of
+        let tp = packet.rpk()?;
+        let id = peer.lock().unwrap().id();
+
+        if let PacketType::CLIENT_HDDOOR_TOGGLE = tp {
+            let eid = packet.ru16()?;
+
+            {
+                let mut peer = peer.lock().unwrap();
+                let dead = peer.player.as_ref().unwrap().dead;
+                assert_or_disconnect!(!dead, &mut peer);
+            }
+
+            let entities = game.entities.clone();
+            let mut entities = entities.lock().unwrap();
+            let door = match entities.get_mut(&eid) {
+                Some(res) if res.id() == "hddoor" => res.as_any_mut().downcast_mut::<HDDoor>().unwrap(),
+                _ => return Ok(())
+            };
+
+            // Tell the player the real state instead
+            if door.cooldown > 0 {
+                peer.lock().unwrap().send(&mut door.state(eid));
+                return Ok(());
+            }
+
+            door.toggle();
+            server.multicast_real(&mut door.state(eid));
+            debug!("ID {} toggled door {} (open: {})", id, door.id, door.open);
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "Haunting Dream"
+    }
+
+    fn index(&self) -> usize {
+        17
+    }
+
+    fn ring_count(&self) -> usize {
+        24
+    }
+}
+
+impl HauntingDream
+{
+    pub fn new() -> HauntingDream {
+        HauntingDream { }
+    }
+}
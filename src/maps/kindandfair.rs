@@ -0,0 +1,98 @@
+use std::sync::{Arc, Mutex};
+
+use log::info;
+
+use crate::{map::Map, states::game::Game, server::{Server, Peer, assert_or_disconnect}, entities::kafmonitor::KAFMonitor, packet::{Packet, PacketType}};
+
+const MONITOR_SPAWNS: [(u16, u16); 8] = [
+    (324, 620),
+    (1210, 620),
+    (2086, 364),
+    (2860, 620),
+    (530, 1444),
+    (1580, 1190),
+    (2415, 1444),
+    (1642, 1956)
+];
+
+const MONITOR_RANGE: f32 = 96.0;
+
+pub(crate) struct KindAndFair
+{
+
+}
+
+impl Map for KindAndFair
+{
+    fn init(&mut self, server: &mut Server, game: &mut Game) 
+    {
+        for (id, point) in MONITOR_SPAWNS.iter().enumerate() {
+            game.spawn(server, Box::new(KAFMonitor {
+                id: id as u8,
+                x: point.0,
+                y: point.1,
+                broken: false,
+                timer: 0
+            }));
+        }
+    }
+
+    fn got_tcp_packet(&mut self, server: &mut Server, game: &mut Game, peer: Arc<Mutex<Peer>>, packet: &mut Packet) -> Result<(), &'static str> 
+    {
+        let _passtrough = packet.ru8()? != 0; //TODO: get rid of
+        let tp = packet.rpk()?;
+        let id = peer.lock().unwrap().id();
+
+        if let PacketType::CLIENT_KAFMONITOR_ACTIVATE = tp {
+            let eid = packet.ru16()?;
+
+            {
+                let mut peer = peer.lock().unwrap();
+                let player = peer.player.as_ref().unwrap();
+                let can_open = !player.exe && !player.dead && player.revival_times < 2;
+                assert_or_disconnect!(can_open, &mut peer);
+            }
+
+            let entities = game.entities.clone();
+            let mut entities = entities.lock().unwrap();
+            let monitor = match entities.get_mut(&eid) {
+                Some(res) if res.id() == "kafmonitor" => res.as_any_mut().downcast_mut::<KAFMonitor>().unwrap(),
+                _ => return Ok(())
+            };
+
+            // Already broken or someone else got it first
+            if monitor.broken {
+                return Ok(());
+            }
+
+            if !game.player_near(id, monitor.x as f32, monitor.y as f32, MONITOR_RANGE) {
+                return Ok(());
+            }
+
+            let (mut packet, reward) = monitor.open(eid, id);
+            server.multicast_real(&mut packet);
+            info!("{} (ID {}) opened a monitor: {:?}", peer.lock().unwrap().nickname, id, reward);
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "Kind and Fair"
+    }
+
+    fn index(&self) -> usize {
+        7
+    }
+
+    fn ring_count(&self) -> usize {
+        24
+    }
+}
+
+impl KindAndFair
+{
+    pub fn new() -> KindAndFair {
+        KindAndFair { }
+    }
+}
@@ -0,0 +1,127 @@
+use std::sync::{Arc, Mutex};
+
+use log::debug;
+
+use crate::{map::Map, states::game::{Game, find_entities_mut}, server::{Server, Peer, assert_or_disconnect}, entities::{lceye::{LCEye, EYE_MAX_CHARGE, EYE_MIN_CHARGE}, lcchain::LCChain}, packet::{Packet, PacketType}};
+
+const EYE_COUNT: u8 = 10;
+const CHAIN_COUNT: u8 = 6;
+
+pub(crate) struct LimbCity
+{
+
+}
+
+impl Map for LimbCity
+{
+    fn init(&mut self, server: &mut Server, game: &mut Game) 
+    {
+        for id in 0..EYE_COUNT {
+            game.spawn(server, Box::new(LCEye {
+                id,
+                used_by: None,
+                charge: EYE_MAX_CHARGE,
+                cooldown: 0,
+                sync_timer: 0
+            }));
+        }
+
+        for id in 0..CHAIN_COUNT {
+            game.spawn(server, Box::new(LCChain {
+                id,
+                active: false,
+                timer: 0
+            }));
+        }
+    }
+
+    fn tick(&mut self, server: &mut Server, game: &mut Game) 
+    {
+        // Free eyes of players that died or left
+        let peers = server.peers.read().unwrap().clone();
+        for entity in find_entities_mut!(game.entities.clone().lock().unwrap(), "lceye") {
+            let eye = entity.1.as_any_mut().downcast_mut::<LCEye>().unwrap();
+            let user = match eye.used_by {
+                Some(res) => res,
+                None => continue
+            };
+
+            let alive = match peers.get(&user) {
+                Some(peer) => peer.lock().unwrap().player.as_ref().is_some_and(|x| !x.dead),
+                None => false
+            };
+
+            if !alive {
+                eye.release();
+                server.multicast_real(&mut eye.state(*entity.0));
+            }
+        }
+    }
+
+    fn got_tcp_packet(&mut self, server: &mut Server, game: &mut Game, peer: Arc<Mutex<Peer>>, packet: &mut Packet) -> Result<(), &'static str> 
+    {
+        let _passtrough = packet.ru8()? != 0; //TODO: get rid of
+        let tp = packet.rpk()?;
+        let id = peer.lock().unwrap().id();
+
+        if let PacketType::CLIENT_LCEYE_REQUEST_ACTIVATE = tp {
+            let eid = packet.ru16()?;
+            let using = packet.ru8()? != 0;
+
+            if using {
+                let mut peer = peer.lock().unwrap();
+                let player = peer.player.as_ref().unwrap();
+                let can_use = !player.exe && !player.dead && player.revival_times < 2;
+                assert_or_disconnect!(can_use, &mut peer);
+            }
+
+            let entities = game.entities.clone();
+            let mut entities = entities.lock().unwrap();
+
+            // One eye at a time
+            let busy = entities.iter().any(|x| x.1.id() == "lceye" && *x.0 != eid && x.1.as_any().downcast_ref::<LCEye>().unwrap().used_by == Some(id));
+
+            let eye = match entities.get_mut(&eid) {
+                Some(res) if res.id() == "lceye" => res.as_any_mut().downcast_mut::<LCEye>().unwrap(),
+                _ => return Ok(())
+            };
+
+            if using {
+                if eye.used_by.is_none() && eye.cooldown == 0 && eye.charge >= EYE_MIN_CHARGE && !busy {
+                    eye.used_by = Some(id);
+                    server.multicast_real(&mut eye.state(eid));
+                    debug!("ID {} uses eye {}", id, eye.id);
+                }
+                else {
+                    // Let the client know it was refused
+                    peer.lock().unwrap().send(&mut eye.state(eid));
+                }
+            }
+            else if eye.used_by == Some(id) {
+                eye.release();
+                server.multicast_real(&mut eye.state(eid));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "Limb City"
+    }
+
+    fn index(&self) -> usize {
+        5
+    }
+
+    fn ring_count(&self) -> usize {
+        26
+    }
+}
+
+impl LimbCity
+{
+    pub fn new() -> LimbCity {
+        LimbCity { }
+    }
+}
@@ -1,2 +1,68 @@
 pub mod hideandseek2;
-pub mod ravinemist;
\ No newline at end of file
+pub mod ravinemist;
+pub mod limbcity;
+pub mod nastyparadise;
+pub mod kindandfair;
+pub mod youcantrun;
+pub mod act9;
+pub mod pricefield;
+pub mod volcanovalley;
+pub mod greenhill;
+pub mod torturecave;
+pub mod deserttown;
+pub mod hauntingdream;
+pub mod fartzone;
+pub mod notperfect;
+
+use std::sync::{Arc, Mutex};
+
+use lazy_static::lazy_static;
+
+use crate::map::Map;
+
+type Constructor = fn() -> Arc<Mutex<dyn Map>>;
+
+lazy_static! {
+    static ref NAMES: Vec<(usize, String)> = all().iter().map(|x| {
+        let map = x.lock().unwrap();
+        (map.index(), map.name().to_string())
+    }).collect();
+}
+
+// Every playable map, in index order
+const REGISTRY: [Constructor; 15] = [
+    || Arc::new(Mutex::new(hideandseek2::HideAndSeek2::new())),
+    || Arc::new(Mutex::new(ravinemist::RavineMist::new())),
+    || Arc::new(Mutex::new(deserttown::DesertTown::new())),
+    || Arc::new(Mutex::new(youcantrun::YouCantRun::new())),
+    || Arc::new(Mutex::new(limbcity::LimbCity::new())),
+    || Arc::new(Mutex::new(notperfect::NotPerfect::new())),
+    || Arc::new(Mutex::new(kindandfair::KindAndFair::new())),
+    || Arc::new(Mutex::new(act9::Act9::new())),
+    || Arc::new(Mutex::new(nastyparadise::NastyParadise::new())),
+    || Arc::new(Mutex::new(pricefield::Pricefield::new())),
+    || Arc::new(Mutex::new(volcanovalley::VolcanoValley::new())),
+    || Arc::new(Mutex::new(greenhill::GreenHill::new())),
+    || Arc::new(Mutex::new(torturecave::TortureCave::new())),
+    || Arc::new(Mutex::new(hauntingdream::HauntingDream::new())),
+    || Arc::new(Mutex::new(fartzone::FartZone::new()))
+];
+
+// Fresh instances, maps keep state for a single round
+pub(crate) fn all() -> Vec<Arc<Mutex<dyn Map>>>
+{
+    REGISTRY.iter().map(|x| x()).collect()
+}
+
+// (index, name) of every map, so lookups don't build them each time
+pub(crate) fn list() -> &'static [(usize, String)]
+{
+    &NAMES
+}
+
+// Looks up a map by it's name (case insensitive) or index
+pub(crate) fn find(key: &str) -> Option<(usize, &'static str)>
+{
+    let key = key.trim();
+    NAMES.iter().find(|x| x.1.eq_ignore_ascii_case(key) || x.0.to_string() == key).map(|x| (x.0, x.1.as_str()))
+}
@@ -0,0 +1,128 @@
+use std::{sync::{Arc, Mutex}, collections::HashMap};
+
+use log::debug;
+use rand::{thread_rng, Rng};
+
+use crate::{map::Map, states::game::{Game, find_entities}, server::{Server, Peer, assert_or_disconnect}, entities::{napball::NAPBall, napice::NAPIce}, packet::{Packet, PacketType}};
+
+// x, y, direction
+const BALL_SPAWNS: [(f32, f32, i8); 4] = [
+    (410.0, 846.0, 1),
+    (2935.0, 846.0, -1),
+    (640.0, 1680.0, 1),
+    (2700.0, 1680.0, -1)
+];
+
+const BALL_MAX: usize = 3;
+const ICE_COUNT: u8 = 8;
+const ICE_PLAYER_COOLDOWN: u16 = 2 * 60; // Between two activations of the same player
+
+pub(crate) struct NastyParadise
+{
+    ball_timer: u16,
+    ice_timers: HashMap<u16, u16>
+}
+
+impl Map for NastyParadise
+{
+    fn init(&mut self, server: &mut Server, game: &mut Game) 
+    {
+        self.ball_timer = thread_rng().gen_range(5..15) * 60;
+
+        for id in 0..ICE_COUNT {
+            game.spawn(server, Box::new(NAPIce {
+                id,
+                active: false,
+                timer: 0,
+                activated_by: 0
+            }));
+        }
+    }
+
+    fn tick(&mut self, server: &mut Server, game: &mut Game) 
+    {
+        for timer in self.ice_timers.values_mut() {
+            *timer = timer.saturating_sub(1);
+        }
+
+        self.ball_timer -= 1;
+        if self.ball_timer > 0 {
+            return;
+        }
+
+        self.ball_timer = thread_rng().gen_range(10..20) * 60;
+        if find_entities!(game.entities.lock().unwrap(), "napball").count() >= BALL_MAX {
+            return;
+        }
+
+        let point = BALL_SPAWNS[thread_rng().gen_range(0..BALL_SPAWNS.len())];
+        game.spawn(server, Box::new(NAPBall {
+            x: point.0,
+            y: point.1,
+            dir: point.2,
+            timer: 12 * 60
+        }));
+    }
+
+    fn got_tcp_packet(&mut self, server: &mut Server, game: &mut Game, peer: Arc<Mutex<Peer>>, packet: &mut Packet) -> Result<(), &'static str> 
+    {
+        let _passtrough = packet.ru8()? != 0; //TODO: get rid of
+        let tp = packet.rpk()?;
+        let id = peer.lock().unwrap().id();
+
+        if let PacketType::CLIENT_NAPICE_ACTIVATE = tp {
+            let eid = packet.ru16()?;
+
+            {
+                let mut peer = peer.lock().unwrap();
+                let dead = peer.player.as_ref().unwrap().dead;
+                assert_or_disconnect!(!dead, &mut peer);
+            }
+
+            // Spamming the packet does nothing
+            if self.ice_timers.get(&id).copied().unwrap_or(0) > 0 {
+                debug!("ID {} activates ice too fast", id);
+                return Ok(());
+            }
+
+            let entities = game.entities.clone();
+            let mut entities = entities.lock().unwrap();
+            let ice = match entities.get_mut(&eid) {
+                Some(res) if res.id() == "napice" => res.as_any_mut().downcast_mut::<NAPIce>().unwrap(),
+                _ => return Ok(())
+            };
+
+            if !ice.ready() {
+                return Ok(());
+            }
+
+            ice.activate(id);
+            server.multicast_real(&mut ice.state(eid));
+            self.ice_timers.insert(id, ICE_PLAYER_COOLDOWN);
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "Nasty Paradise"
+    }
+
+    fn index(&self) -> usize {
+        9
+    }
+
+    fn ring_count(&self) -> usize {
+        24
+    }
+}
+
+impl NastyParadise
+{
+    pub fn new() -> NastyParadise {
+        NastyParadise { 
+            ball_timer: 0,
+            ice_timers: HashMap::new()
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::{map::Map, states::game::Game, server::Server, entities::npcontroller::NPController};
+
+pub(crate) struct NotPerfect
+{
+
+}
+
+impl Map for NotPerfect
+{
+    fn init(&mut self, server: &mut Server, game: &mut Game) 
+    {
+        game.spawn(server, Box::new(NPController {
+            phase: 0,
+            timer: 0,
+            sync_timer: 60
+        }));
+    }
+
+    fn name(&self) -> &str {
+        "Not Perfect"
+    }
+
+    fn index(&self) -> usize {
+        6
+    }
+
+    fn ring_count(&self) -> usize {
+        23
+    }
+}
+
+impl NotPerfect
+{
+    pub fn new() -> NotPerfect {
+        NotPerfect { }
+    }
+}
@@ -0,0 +1,103 @@
+use std::sync::{Arc, Mutex};
+
+use log::debug;
+
+use crate::{map::Map, states::game::Game, server::{Server, Peer, assert_or_disconnect}, entities::pflift::{PFLift, LiftState}, packet::{Packet, PacketType}};
+
+// x, bottom, top
+const LIFTS: [(u16, u16, u16); 4] = [
+    (702, 1630, 1040),
+    (1488, 1630, 784),
+    (2265, 1886, 1296),
+    (3012, 1118, 528)
+];
+
+// Player has to stand on the lift
+const LIFT_WIDTH: f32 = 64.0;
+const LIFT_HEIGHT: f32 = 48.0;
+
+pub(crate) struct Pricefield
+{
+
+}
+
+impl Map for Pricefield
+{
+    fn init(&mut self, server: &mut Server, game: &mut Game) 
+    {
+        for (id, lift) in LIFTS.iter().enumerate() {
+            game.spawn(server, Box::new(PFLift {
+                id: id as u8,
+                x: lift.0,
+                y: lift.1,
+                bottom: lift.1,
+                top: lift.2,
+                state: LiftState::Idle,
+                timer: 0,
+                rider: 0
+            }));
+        }
+    }
+
+    fn got_tcp_packet(&mut self, server: &mut Server, game: &mut Game, peer: Arc<Mutex<Peer>>, packet: &mut Packet) -> Result<(), &'static str> 
+    {
+        let _passtrough = packet.ru8()? != 0; //TODO: get rid of
+        let tp = packet.rpk()?;
+        let id = peer.lock().unwrap().id();
+
+        if let PacketType::CLIENT_PFLIT_ACTIVATE = tp {
+            let eid = packet.ru16()?;
+
+            {
+                let mut peer = peer.lock().unwrap();
+                let dead = peer.player.as_ref().unwrap().dead;
+                assert_or_disconnect!(!dead, &mut peer);
+            }
+
+            let entities = game.entities.clone();
+            let mut entities = entities.lock().unwrap();
+            let lift = match entities.get_mut(&eid) {
+                Some(res) if res.id() == "pflift" => res.as_any_mut().downcast_mut::<PFLift>().unwrap(),
+                _ => return Ok(())
+            };
+
+            if !lift.ready() {
+                return Ok(());
+            }
+
+            let riding = match game.players_pos.get(&id) {
+                Some(pos) if *pos != (0.0, 0.0) => (pos.0 - lift.x as f32).abs() <= LIFT_WIDTH && (pos.1 - lift.y as f32).abs() <= LIFT_HEIGHT,
+                _ => false
+            };
+
+            if !riding {
+                debug!("ID {} isn't on lift {}", id, lift.id);
+                return Ok(());
+            }
+
+            lift.activate(id);
+            server.multicast_real(&mut lift.state(eid));
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "Pricefield"
+    }
+
+    fn index(&self) -> usize {
+        10
+    }
+
+    fn ring_count(&self) -> usize {
+        26
+    }
+}
+
+impl Pricefield
+{
+    pub fn new() -> Pricefield {
+        Pricefield { }
+    }
+}
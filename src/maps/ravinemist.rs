@@ -2,7 +2,13 @@ use std::{sync::{Arc, Mutex}, collections::HashMap};
 
 use rand::{thread_rng, Rng, seq::SliceRandom};
 
-use crate::{map::Map, states::game::{Game, find_entities}, server::{Server, Peer, real_peers}, entities::{slug::{Slug, SlugState, SlugRing}, shard::Shard}, packet::{Packet, PacketType}};
+use log::info;
+
+use crate::{map::Map, states::game::{Game, find_entities}, server::{Server, Peer, real_peers, assert_or_disconnect}, entities::{slug::{Slug, SlugState, SlugRing}, shard::Shard}, packet::{Packet, PacketType}};
+
+const SHARD_COUNT: usize = 7;
+const SHARD_RANGE: f32 = 160.0; // Positions come over UDP, so be generous
+const SHARD_DROP_TIME: u16 = 2 * 60; // Wait for CLIENT_RMZSHARD_LAND this long
 
 const SLUG_SPAWNS: [(i32, i32, bool); 11] = [
     (1901, 392, false),
@@ -38,7 +44,9 @@ pub(crate) struct RavineMist
     slugs: [(i32, i32, bool); 11],
     slug_timer: u16,
 
-    shards_list: HashMap<u16, u8>
+    shards_list: HashMap<u16, u8>,
+    shards_drop: HashMap<u16, u16>, // Dead players that still hold shards
+    shards_bonus: bool
 }
 
 impl Map for RavineMist
@@ -55,7 +63,7 @@ impl Map for RavineMist
         // Randomly shuffle and spawn shards
         let mut spawns = SHARD_SPAWNS.clone();
         spawns.shuffle(&mut thread_rng());
-        for point in spawns.iter().take(7) {
+        for point in spawns.iter().take(SHARD_COUNT) {
             game.spawn(server, Box::new(Shard {
                 x: point.0,
                 y: point.1,
@@ -91,13 +99,72 @@ impl Map for RavineMist
 
             self.slug_timer = (15 * 60) + (thread_rng().gen_range(2..10) * 60);
         }
+
+        self.check_holders(server, game);
     }
 
-    fn got_tcp_packet(&mut self, _server: &mut Server, game: &mut Game, peer: Arc<Mutex<Peer>>, packet: &mut Packet) -> Result<(), &'static str> {
+    fn got_tcp_packet(&mut self, server: &mut Server, game: &mut Game, peer: Arc<Mutex<Peer>>, packet: &mut Packet) -> Result<(), &'static str> {
         let _passtrough = packet.ru8()? != 0; //TODO: get rid of
         let tp = packet.rpk()?;
+        let id = peer.lock().unwrap().id();
 
         match tp {
+            PacketType::CLIENT_RMZSHARD_COLLECT => {
+                let eid = packet.ru16()?;
+
+                {
+                    let mut peer = peer.lock().unwrap();
+                    let player = peer.player.as_ref().unwrap();
+                    let can_collect = !player.exe && !player.dead && player.revival_times < 2;
+                    assert_or_disconnect!(can_collect, &mut peer);
+                }
+
+                let mut shard = None;
+                for entity in find_entities!(game.entities.clone().lock().unwrap(), "shard") {
+                    if *entity.0 != eid {
+                        continue;
+                    }
+
+                    let entity = entity.1.as_any().downcast_ref::<Shard>().unwrap();
+                    shard = Some((entity.x, entity.y));
+                    break;
+                }
+
+                // Someone else was faster
+                let (x, y) = match shard {
+                    Some(res) => res,
+                    None => return Ok(())
+                };
+
+                if !game.player_near(id, x as f32, y as f32, SHARD_RANGE) {
+                    return Ok(());
+                }
+
+                game.queue_destroy(&eid);
+                *self.shards_list.entry(id).or_insert(0) += 1;
+                self.send_count(server, id);
+
+                info!("{} (ID {}) collected a shard ({}/{})", peer.lock().unwrap().nickname, id, self.shards_list[&id], SHARD_COUNT);
+                self.check_bonus(server, game);
+            },
+
+            // Where shards of a dead player fell
+            PacketType::CLIENT_RMZSHARD_LAND => {
+                let x = packet.ru16()?;
+                let y = packet.ru16()?;
+
+                if self.shards_drop.remove(&id).is_some() {
+                    self.drop_shards(server, game, id, x, y);
+                }
+            },
+
+            PacketType::CLIENT_PLAYER_DEATH_STATE => {
+                let dead = peer.lock().unwrap().player.as_ref().unwrap().dead;
+                if dead && self.shards_list.get(&id).copied().unwrap_or(0) > 0 {
+                    self.shards_drop.insert(id, SHARD_DROP_TIME);
+                }
+            },
+
             PacketType::CLIENT_RMZSLIME_HIT => {
                 let eid = packet.ru16()?;
                 let proj = packet.ru8()? != 0;
@@ -160,7 +227,70 @@ impl RavineMist
         RavineMist { 
             slugs: SLUG_SPAWNS.clone(),
             slug_timer: 0,
-            shards_list: HashMap::new()
+            shards_list: HashMap::new(),
+            shards_drop: HashMap::new(),
+            shards_bonus: false
         }
     }
+
+    fn send_count(&mut self, server: &mut Server, id: u16)
+    {
+        let mut packet = Packet::new(PacketType::SERVER_RMZSHARD_STATE);
+        packet.wu8(3);
+        packet.wu16(id);
+        packet.wu8(self.shards_list.get(&id).copied().unwrap_or(0));
+        server.multicast_real(&mut packet);
+    }
+
+    fn drop_shards(&mut self, server: &mut Server, game: &mut Game, id: u16, x: u16, y: u16)
+    {
+        let count = self.shards_list.insert(id, 0).unwrap_or(0);
+        if count == 0 {
+            return;
+        }
+
+        for _ in 0..count {
+            game.spawn(server, Box::new(Shard { x, y, spawned: true }));
+        }
+
+        self.send_count(server, id);
+        info!("ID {} dropped {} shard(s)", id, count);
+    }
+
+    // Shards of players that left or never reported the landing point are dropped where they were last seen
+    fn check_holders(&mut self, server: &mut Server, game: &mut Game)
+    {
+        let mut lost = Vec::new();
+        for (id, count) in self.shards_list.iter() {
+            if *count > 0 && !server.peers.read().unwrap().contains_key(id) {
+                lost.push(*id);
+            }
+        }
+
+        for timer in self.shards_drop.iter_mut() {
+            *timer.1 = timer.1.saturating_sub(1);
+            if *timer.1 == 0 {
+                lost.push(*timer.0);
+            }
+        }
+
+        for id in lost {
+            self.shards_drop.remove(&id);
+
+            let pos = game.players_pos.get(&id).copied().unwrap_or((0.0, 0.0));
+            self.drop_shards(server, game, id, pos.0 as u16, pos.1 as u16);
+        }
+    }
+
+    // Gathering every shard opens the big ring early
+    fn check_bonus(&mut self, server: &mut Server, game: &mut Game)
+    {
+        if self.shards_bonus || self.shards_list.values().map(|x| *x as usize).sum::<usize>() < SHARD_COUNT {
+            return;
+        }
+
+        self.shards_bonus = true;
+        game.activate_big_ring(server);
+        info!("All shards collected!");
+    }
 }
\ No newline at end of file
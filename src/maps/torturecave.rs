@@ -0,0 +1,36 @@
+use crate::{map::Map, states::game::Game, server::Server, entities::tcgom::{TCGom, GomState}};
+
+pub(crate) struct TortureCave
+{
+
+}
+
+impl Map for TortureCave
+{
+    fn init(&mut self, server: &mut Server, game: &mut Game) 
+    {
+        game.spawn(server, Box::new(TCGom {
+            state: GomState::Sleeping,
+            timer: 0
+        }));
+    }
+
+    fn name(&self) -> &str {
+        "Torture Cave"
+    }
+
+    fn index(&self) -> usize {
+        15
+    }
+
+    fn ring_count(&self) -> usize {
+        22
+    }
+}
+
+impl TortureCave
+{
+    pub fn new() -> TortureCave {
+        TortureCave { }
+    }
+}
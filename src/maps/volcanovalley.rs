@@ -0,0 +1,110 @@
+use std::sync::{Arc, Mutex};
+
+use log::debug;
+
+use crate::{map::Map, states::game::Game, server::{Server, Peer, assert_or_disconnect}, entities::{vvlcolumn::{VVLColumn, ColumnState}, vvvase::VVVase}, packet::{Packet, PacketType}};
+
+const COLUMN_COUNT: u8 = 7;
+
+const VASE_SPAWNS: [(u16, u16); 10] = [
+    (388, 1204),
+    (842, 948),
+    (1266, 1460),
+    (1730, 692),
+    (2102, 1204),
+    (2588, 948),
+    (3020, 1460),
+    (1140, 2228),
+    (1990, 2228),
+    (2750, 1972)
+];
+
+const VASE_RANGE: f32 = 96.0;
+
+pub(crate) struct VolcanoValley
+{
+
+}
+
+impl Map for VolcanoValley
+{
+    // Vases give rings too, so spawn a bit less of them
+    fn ring_time(&self, server: &Server) -> f32 {
+        360.0 - (60.0 * self.player_time_multiplier(server) * 0.25)
+    }
+
+    fn init(&mut self, server: &mut Server, game: &mut Game) 
+    {
+        for id in 0..COLUMN_COUNT {
+            game.spawn(server, Box::new(VVLColumn {
+                id,
+                state: ColumnState::Idle,
+                timer: 0
+            }));
+        }
+
+        for (id, point) in VASE_SPAWNS.iter().enumerate() {
+            game.spawn(server, Box::new(VVVase {
+                id: id as u8,
+                x: point.0,
+                y: point.1,
+                broken: false
+            }));
+        }
+    }
+
+    fn got_tcp_packet(&mut self, server: &mut Server, game: &mut Game, peer: Arc<Mutex<Peer>>, packet: &mut Packet) -> Result<(), &'static str> 
+    {
+        let _passtrough = packet.ru8()? != 0; //TODO: get rid of
+        let tp = packet.rpk()?;
+        let id = peer.lock().unwrap().id();
+
+        if let PacketType::CLIENT_VVVASE_BREAK = tp {
+            let eid = packet.ru16()?;
+
+            {
+                let mut peer = peer.lock().unwrap();
+                let dead = peer.player.as_ref().unwrap().dead;
+                assert_or_disconnect!(!dead, &mut peer);
+            }
+
+            let entities = game.entities.clone();
+            let mut entities = entities.lock().unwrap();
+            let vase = match entities.get_mut(&eid) {
+                Some(res) if res.id() == "vvvase" => res.as_any_mut().downcast_mut::<VVVase>().unwrap(),
+                _ => return Ok(())
+            };
+
+            if !game.player_near(id, vase.x as f32, vase.y as f32, VASE_RANGE) {
+                return Ok(());
+            }
+
+            // Second player to hit it gets nothing
+            if let Some((mut packet, content)) = vase.break_by(eid, id) {
+                server.multicast_real(&mut packet);
+                debug!("ID {} broke vase {}: {:?}", id, vase.id, content);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "Volcano Valley"
+    }
+
+    fn index(&self) -> usize {
+        11
+    }
+
+    fn ring_count(&self) -> usize {
+        20
+    }
+}
+
+impl VolcanoValley
+{
+    pub fn new() -> VolcanoValley {
+        VolcanoValley { }
+    }
+}
@@ -0,0 +1,92 @@
+use rand::{thread_rng, Rng};
+
+use crate::{config, map::Map, states::game::{Game, find_entities}, server::Server, entities::ycrsmoke::YCRSmoke};
+
+const SMOKE_SPAWNS: [(u16, u16); 6] = [
+    (480, 1120),
+    (1320, 860),
+    (2040, 1380),
+    (2790, 990),
+    (1010, 1890),
+    (2470, 1950)
+];
+
+const SMOKE_MAX: usize = 2;
+
+pub(crate) struct YouCantRun
+{
+    smoke_timer: u16
+}
+
+impl Map for YouCantRun
+{
+    // Rounds here are shorter
+    fn timer(&self, server: &Server) -> f32 {
+        let rules = config::get().rules(&server.name, Some(self.name()));
+        (rules.round_time as f32 * 0.8 + rules.round_time_bonus as f32 * self.player_time_multiplier(server)) * 60.0
+    }
+
+    fn spawn_red_rings(&self) -> bool {
+        false
+    }
+
+    fn bring_activate_time(&self) -> u16 {
+        (60 - 20) * 60
+    }
+
+    fn init(&mut self, _server: &mut Server, _game: &mut Game) 
+    {
+        self.smoke_timer = thread_rng().gen_range(15..25) * 60;
+    }
+
+    fn tick(&mut self, server: &mut Server, game: &mut Game) 
+    {
+        self.smoke_timer -= 1;
+        if self.smoke_timer > 0 {
+            return;
+        }
+
+        self.smoke_timer = thread_rng().gen_range(20..35) * 60;
+
+        // Don't stack smoke on the same spot
+        let taken: Vec<(u16, u16)> = find_entities!(game.entities.lock().unwrap(), "ycrsmoke").map(|x| {
+            let smoke = x.1.as_any().downcast_ref::<YCRSmoke>().unwrap();
+            (smoke.x, smoke.y)
+        }).collect();
+
+        if taken.len() >= SMOKE_MAX {
+            return;
+        }
+
+        let free: Vec<&(u16, u16)> = SMOKE_SPAWNS.iter().filter(|x| !taken.contains(x)).collect();
+        let point = *free[thread_rng().gen_range(0..free.len())];
+
+        game.spawn(server, Box::new(YCRSmoke {
+            x: point.0,
+            y: point.1,
+            active: false,
+            timer: 0
+        }));
+    }
+
+    fn name(&self) -> &str {
+        "You Can't Run"
+    }
+
+    fn index(&self) -> usize {
+        4
+    }
+
+    fn ring_count(&self) -> usize {
+        21
+    }
+}
+
+impl YouCantRun
+{
+    pub fn new() -> YouCantRun {
+        YouCantRun { 
+            smoke_timer: 0
+        }
+    }
+}
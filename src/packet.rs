@@ -141,6 +141,8 @@ pub(crate) enum PacketType
     CLIENT_PET_PALLETE,
 
     CLIENT_PLAYER_POTATER,
+
+    SERVER_INFO_CHALLENGE,
 }
 
 pub(crate) struct Packet
@@ -0,0 +1,154 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::net::{IpAddr, UdpSocket};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use log::{info, warn, debug};
+use rand::{thread_rng, Rng};
+use serde::Serialize;
+
+use crate::{balancer::SERVERS, config, packet::{Packet, PacketType}, states::lobby::BUILD_VER};
+
+const CACHE_TIME: Duration = Duration::from_secs(1);
+const CHALLENGE_TIME: u64 = 30; // Seconds a challenge stays valid for (at least)
+
+// Server browsers can ask for room list without doing identity
+pub(crate) fn start()
+{
+    let port = config::get().server.tcp_port;
+    let socket = match UdpSocket::bind(format!("0.0.0.0:{}", port)) {
+        Ok(res) => res,
+        Err(err) => {
+            warn!("Failed to bind query socket: {}", err);
+            return;
+        }
+    };
+
+    let _ = thread::Builder::new().name("query".to_string()).spawn(move || {
+        info!("Answering info requests at {}/udp", port);
+        worker(socket);
+    });
+}
+
+fn worker(socket: UdpSocket)
+{
+    let secret: u64 = thread_rng().gen();
+    let mut cache: Option<(Instant, Vec<u8>)> = None;
+
+    loop {
+        let mut buf = [0; 16];
+        let (size, src) = match socket.recv_from(&mut buf)
+        {
+            Ok(res) => res,
+            Err(err) => {
+                warn!("Failed to read from query socket: {}", err);
+                continue;
+            }
+        };
+
+        // [passtrough, type, challenge], first request uses any challenge
+        if size < 6 || buf[1] != PacketType::SERVER_REQUEST_INFO as u8 {
+            continue;
+        }
+
+        let mut packet = Packet::from(&buf, size);
+        packet.rewind(2);
+        let challenge = packet.ru32().unwrap_or(0);
+
+        // Reply is way bigger than the request, so the sender has to prove
+        // it owns the address first (same as A2S)
+        let epoch = SystemTime::now().duration_since(UNIX_EPOCH).map(|x| x.as_secs()).unwrap_or(0) / CHALLENGE_TIME;
+        if challenge != make_challenge(secret, src.ip(), epoch) && challenge != make_challenge(secret, src.ip(), epoch.saturating_sub(1)) {
+            let mut packet = Packet::new(PacketType::SERVER_INFO_CHALLENGE);
+            packet.wu32(make_challenge(secret, src.ip(), epoch));
+
+            if let Err(err) = socket.send_to(packet.raw(), src) {
+                warn!("Failed to send challenge to {}: {}", src, err);
+            }
+            continue;
+        }
+
+        // Don't lock every sub-server on each request
+        let raw = match &cache {
+            Some((time, raw)) if time.elapsed() < CACHE_TIME => raw,
+            _ => &cache.insert((Instant::now(), info_packet().raw().to_vec())).1
+        };
+
+        debug!("Info request from {}", src);
+        if let Err(err) = socket.send_to(raw, src) {
+            warn!("Failed to send info to {}: {}", src, err);
+        }
+    }
+}
+
+// Stateless, so spoofed requests don't cost any memory
+fn make_challenge(secret: u64, addr: IpAddr, epoch: u64) -> u32
+{
+    let mut hasher = DefaultHasher::new();
+    (secret, addr, epoch).hash(&mut hasher);
+    hasher.finish() as u32
+}
+
+#[derive(Serialize)]
+pub(crate) struct ServerInfo
+{
+    pub name: String,
+    pub state: String,
+    pub map: String,
+    pub players: Vec<String>,
+    pub max_players: u8
+}
+
+// Public sub-servers as they are right now
+pub(crate) fn snapshot() -> Vec<ServerInfo>
+{
+    // Private rooms aren't listed
+    let servers: Vec<_> = SERVERS.read().unwrap().iter().filter(|x| x.code.is_none()).cloned().collect();
+    let mut result = Vec::new();
+
+    for sub in servers {
+        let server = sub.server.lock().unwrap();
+        let state = server.state.lock().unwrap();
+
+        let map = match state.map() {
+            Some(map) => map.lock().unwrap().name().to_string(),
+            None => String::new()
+        };
+
+        let max_players = config::get().rules(&server.name, None).max_players;
+
+        let players = server.peers.read().unwrap().values().filter_map(|x| {
+            let peer = x.lock().unwrap();
+            if peer.pending { None } else { Some(peer.nickname.clone()) }
+        }).collect();
+
+        result.push(ServerInfo { name: server.name.clone(), state: state.name().to_string(), map, players, max_players });
+    }
+
+    result
+}
+
+fn info_packet() -> Packet
+{
+    // Count is a single byte, the rest doesn't fit
+    let servers: Vec<ServerInfo> = snapshot().into_iter().take(u8::MAX as usize).collect();
+
+    let mut packet = Packet::new(PacketType::CLIENT_REQUESTED_INFO);
+    packet.wu16(BUILD_VER);
+    packet.wu8(servers.len() as u8);
+
+    for info in servers {
+        packet.wstr(&info.name);
+        packet.wstr(&info.state);
+        packet.wstr(&info.map);
+        packet.wu8(info.players.len() as u8);
+        packet.wu8(info.max_players);
+
+        for nickname in info.players {
+            packet.wstr(&nickname);
+        }
+    }
+
+    packet
+}
@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use std::io::{Read, Write};
+use std::io::{self, ErrorKind, Read, Write};
 use std::net::{TcpStream, SocketAddr, UdpSocket};
 use std::num::Wrapping;
 use std::ops::AddAssign;
@@ -11,6 +11,7 @@ use log::{trace, debug, info, warn};
 use num_derive::FromPrimitive;
 use rand::{thread_rng, Rng};
 
+use crate::config;
 use crate::packet::PacketType;
 use crate::states::lobby::Lobby;
 
@@ -61,14 +62,19 @@ pub(crate) struct Server
     pub peers: Arc<RwLock<HashMap<u16, Arc<Mutex<Peer>>>>>,
     pub state: Arc<Mutex<Box<dyn State>>>,
     pub udp_socket: Arc<Mutex<UdpSocket>>,
+    pub map_history: Vec<usize>, // Indexes of played maps, latest last
     
+    running: bool,
     id_count: Arc<Mutex<Wrapping<u16>>>,
     next_state: Arc<Mutex<Option<Mutex<Box<Box<dyn State + 'static>>>>>>
 }
 
 impl Server {
-    pub fn start(udp_port: u16, name: String) -> Arc<Mutex<Server>> 
+    pub fn start(udp_port: u16, name: String) -> io::Result<Arc<Mutex<Server>>> 
     {
+        let socket = UdpSocket::bind(format!("0.0.0.0:{}", udp_port))?;
+        let _ = socket.set_read_timeout(Some(Duration::from_secs(1))); // so udp thread notices stop()
+
         let server = Arc::new(Mutex::new(Server {
             udp_port: udp_port,
             name: name.clone(),
@@ -76,7 +82,9 @@ impl Server {
             peers: Arc::new(RwLock::new(HashMap::new())), 
             state: Arc::new(Mutex::new(Box::new(Lobby::new()))), // Default state - Lobby
 
-            udp_socket: Arc::new(Mutex::new(UdpSocket::bind(format!("0.0.0.0:{}", udp_port)).unwrap())), 
+            udp_socket: Arc::new(Mutex::new(socket)), 
+            map_history: Vec::new(),
+            running: true,
             id_count: Arc::new(Mutex::new(Wrapping(0))),
             next_state: Arc::new(Mutex::new(None))
         }));
@@ -99,7 +107,7 @@ impl Server {
             Server::udp_worker(server_clone, state, listener_clone);
         });
 
-        server
+        Ok(server)
     }
 
     pub fn peer_redirect(server: Arc<Mutex<Server>>, stream: TcpStream) -> bool
@@ -111,18 +119,8 @@ impl Server {
             let state = server.lock().unwrap().state.clone();
 
             // Generate ID
-            let mut _id: u16 = 1;
-            {
-                let server = server.lock().unwrap();
-                let mut id_count = server.id_count.lock().unwrap();
-                id_count.add_assign(1);
-
-                if id_count.0 == 0 {
-                    id_count.0 = 1;
-                }
-
-                _id = id_count.0;
-            }
+            let mut _id: u16 = server.lock().unwrap().next_id();
+            let rules = config::get().rules(&server.lock().unwrap().name, None);
 
             trace!("New connection from {:?} (ID {})", addr, _id);
             let stream_clone = match stream.lock().unwrap().try_clone() {
@@ -142,20 +140,22 @@ impl Server {
                 nickname: String::new(),
                 udid: String::new(),
 
-                exe_chance: thread_rng().gen_range(2..5),
+                exe_chance: thread_rng().gen_range(rules.exe_chance_start.0..rules.exe_chance_start.1),
                 timer: 0, 
                 lobby_icon: 0, 
                 pet: 0,
                 pending: true,
                 in_queue: true,
                 ready: false,
-                player: None
+                player: None,
+                redirect: None
             };
 
             // Listen for messages
             let peer = Arc::new(Mutex::new(peer));
-            let peers = server.lock().unwrap().peers.clone();
-            let server = server.clone();
+            let mut peers = server.lock().unwrap().peers.clone();
+            let mut state = state;
+            let mut server = server.clone();
             info!("{:?} connected. (ID {})", peer.lock().unwrap().addr(), _id);
             Server::connected(&mut server.lock().unwrap(), state.clone(), peer.clone());
 
@@ -168,7 +168,20 @@ impl Server {
             loop {
                 // Reading incoming messages
                 let mut read: usize = 0;
-                match stream.lock().unwrap().read(&mut in_buffer)
+                let result = stream.lock().unwrap().read(&mut in_buffer);
+
+                // Peer was moved to another sub-server while we were waiting
+                let redirect = peer.lock().unwrap().redirect.take();
+                if let Some(next) = redirect {
+                    state = next.lock().unwrap().state.clone();
+                    peers = next.lock().unwrap().peers.clone();
+                    server = next;
+
+                    _id = peer.lock().unwrap().id();
+                    debug!("Peer is now handled by {} (ID {})", server.lock().unwrap().name, _id);
+                }
+
+                match result
                 {
                     Ok(sz) => read = sz,
                     Err(err) => {
@@ -218,13 +231,83 @@ impl Server {
         true
     }
 
+    pub fn stop(&mut self)
+    {
+        for peer in self.peers.read().unwrap().values() {
+            peer.lock().unwrap().disconnect("Server was closed.");
+        }
+
+        self.running = false;
+        info!("Stopped {}", self.name);
+    }
+
+    pub fn peer_transfer(from: &mut Server, to: &mut Server, handle: Arc<Mutex<Server>>, peer: Arc<Mutex<Peer>>)
+    {
+        let old_id = peer.lock().unwrap().id();
+        from.peers.write().unwrap().remove(&old_id);
+
+        let state = from.state.clone();
+        Server::disconnected(from, state, peer.clone());
+
+        // Clear old player list on the client's side
+        for other in from.peers.read().unwrap().values() {
+            let other = other.lock().unwrap();
+            if other.pending {
+                continue;
+            }
+
+            let mut packet = Packet::new(PacketType::SERVER_PLAYER_LEFT);
+            packet.wu16(other.id());
+            peer.lock().unwrap().send(&mut packet);
+        }
+
+        let id = to.next_id();
+        {
+            let mut peer = peer.lock().unwrap();
+            peer.id = id;
+            peer.timer = 0;
+            peer.ready = false;
+            peer.in_queue = false;
+            peer.redirect = Some(handle);
+
+            let mut packet = Packet::new(PacketType::SERVER_IDENTITY_RESPONSE);
+            packet.wu8(true as u8);
+            packet.wu16(to.udp_port);
+            packet.wu16(id);
+            peer.send(&mut packet);
+
+            info!("{} (ID {}) moved from {} to {} (ID {})", peer.nickname, old_id, from.name, to.name, id);
+        }
+
+        to.peers.write().unwrap().insert(id, peer.clone());
+        let state = to.state.clone();
+        Server::transferred(to, state, peer);
+    }
+
+    fn next_id(&self) -> u16
+    {
+        let mut id_count = self.id_count.lock().unwrap();
+        id_count.add_assign(1);
+
+        if id_count.0 == 0 {
+            id_count.0 = 1;
+        }
+
+        id_count.0
+    }
+
     fn udp_worker(server: Arc<Mutex<Server>>, state: Arc<Mutex<Box<dyn State>>>, listener: Arc<Mutex<UdpSocket>>)
     {
         loop {
+            if !server.lock().unwrap().running {
+                break;
+            }
+
             let mut buf = [0; 256];
             let (size, src) = match listener.lock().unwrap().recv_from(&mut buf)
             {
                 Ok(res) => res,
+                Err(err) if err.kind() == ErrorKind::WouldBlock || err.kind() == ErrorKind::TimedOut => continue,
                 Err(err) => {
                     warn!("Failed to read from UDP connection: {}", err);
                     continue;
@@ -246,6 +329,10 @@ impl Server {
             let last_update = Instant::now();
             { 
                 let server = &mut server.lock().unwrap();
+                if !server.running {
+                    break;
+                }
+
                 let next_state = state.lock().unwrap().tick(server);
                 check_state!(next_state, server);
             }
@@ -349,6 +436,12 @@ impl Server {
         check_state!(next_state, server);
     }
 
+    fn transferred(server: &mut Server, state: Arc<Mutex<Box<dyn State>>>, peer: Arc<Mutex<Peer>>) 
+    {
+        let next_state = state.lock().unwrap().transferred(server, peer);
+        check_state!(next_state, server);
+    }
+
     fn got_tcp_packet(server: &mut Server, state: Arc<Mutex<Box<dyn State>>>, peer: Arc<Mutex<Peer>>, packet: &mut Packet) 
     {  
         let result = state.lock().unwrap().got_tcp_packet(server, peer.clone(), packet);
@@ -383,6 +476,7 @@ pub(crate) struct Peer {
     pub player: Option<Player>,
 
     id: u16,
+    redirect: Option<Arc<Mutex<Server>>>,
 
     stream: TcpStream,
     addr: SocketAddr
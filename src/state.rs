@@ -2,7 +2,7 @@ use std::{sync::{Arc, Mutex}, net::SocketAddr};
 
 use log::debug;
 
-use crate::{server::{Server, Peer}, packet::{Packet, PacketType}, states::lobby::BUILD_VER};
+use crate::{config, server::{Server, Peer}, packet::{Packet, PacketType}, states::lobby::BUILD_VER, map::Map};
 
 pub(crate) trait State: Send + Sync
 {
@@ -11,6 +11,7 @@ pub(crate) trait State: Send + Sync
 
     fn connect(&mut self, _server: &mut Server, _peer: Arc<Mutex<Peer>>) -> Option<Box<dyn State>>;
     fn disconnect(&mut self, _server: &mut Server, _peer: Arc<Mutex<Peer>>) -> Option<Box<dyn State>>;
+    fn transferred(&mut self, _server: &mut Server, _peer: Arc<Mutex<Peer>>) -> Option<Box<dyn State>> { None }
     fn got_tcp_packet(&mut self, _server: &mut Server, _peer: Arc<Mutex<Peer>>, _packet: &mut Packet) -> Result<(), &'static str>;
     fn got_udp_packet(&mut self, _server: &mut Server, _addr: &SocketAddr, _packet: &mut Packet) -> Result<(), &'static str> { Ok(()) }
 
@@ -42,6 +43,12 @@ pub(crate) trait State: Send + Sync
             peer.pet = packet.ri8()?;
             let os_type = packet.ru8()?;
             peer.udid = packet.rstr()?;
+
+            if config::get().bans.contains(&peer.udid) {
+                peer.disconnect("You are banned.");
+                return Ok(false);
+            }
+
             debug!("Identity of \"{}\" (ID {}):", peer.nickname, peer.id());
             debug!("OS: {} UDID: {}", os_type, peer.udid);
             
@@ -62,5 +69,6 @@ pub(crate) trait State: Send + Sync
         Ok(true)
     }
 
+    fn map(&self) -> Option<Arc<Mutex<dyn Map>>> { None }
     fn name(&self) -> &str { "default" }
 }
\ No newline at end of file
@@ -5,6 +5,7 @@ use log::{info, debug};
 use num_traits::FromPrimitive;
 use rand::{thread_rng, Rng};
 
+use crate::config;
 use crate::map::Map;
 use crate::packet::{Packet, PacketType};
 use crate::state::State;
@@ -27,6 +28,7 @@ impl State for CharacterSelect
     fn init(&mut self, server: &mut Server) -> Option<Box<dyn State>> 
     {
         self.exe = self.choose_exe(server);
+        let rules = config::get().rules(&server.name, Some(self.map.lock().unwrap().name()));
 
         let mut packet = Packet::new(PacketType::SERVER_LOBBY_EXE);
         packet.wu16(self.exe);
@@ -37,7 +39,7 @@ impl State for CharacterSelect
             let mut peer = peer.lock().unwrap();
             peer.player = Some(Player::new()); // new player
             peer.player.as_mut().unwrap().exe = self.exe == peer.id();
-            peer.timer = 30;
+            peer.timer = rules.character_timeout;
         }
 
         let peers = server.peers.read().unwrap();
@@ -220,6 +222,10 @@ impl State for CharacterSelect
         Ok(())
     }
 
+    fn map(&self) -> Option<Arc<Mutex<dyn Map>>> {
+        Some(self.map.clone())
+    }
+
     fn name(&self) -> &str {
         "Character Select"
     }
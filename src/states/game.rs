@@ -4,8 +4,11 @@ use std::net::SocketAddr;
 use std::sync::{Mutex, Arc};
 
 use log::{info, warn, debug};
+use rand::seq::SliceRandom;
 use rand::{thread_rng, Rng};
 
+use crate::balancer;
+use crate::config::{self, Rules};
 use crate::entities::blackring::BlackRing;
 use crate::entities::creamring::CreamRing;
 use crate::entities::eggtrack::EggmanTracker;
@@ -52,6 +55,7 @@ pub(crate) enum GameTimer
     EggmanTracker, // Eggman's tracker place cooldown
     CreamRing, // Cream's ring spawn cooldown
     ExetiorRing, // Exetior's black ring place cooldown
+    RingSpawn, // Time until next rings appear
 }
 
 pub(crate) struct Game
@@ -78,7 +82,10 @@ pub(crate) struct Game
     entity_destroy_queue: Vec<u16>,
 
     // Player
-    pub players_pos: HashMap<u16, (f32, f32)>
+    pub players_pos: HashMap<u16, (f32, f32)>,
+
+    // Taken when the round starts, reloads apply to the next one
+    pub rules: Rules
 }
 
 impl State for Game
@@ -90,6 +97,7 @@ impl State for Game
 
         {
             let map = self.map.lock().unwrap();
+            self.rules = config::get().rules(&server.name, Some(map.name()));
             
             self.timer.set(GameTimer::RoundTime, map.timer(&server) as u16);
             
@@ -223,7 +231,7 @@ impl State for Game
                     if revival_times == 1 || (revival_times == 0 && self.timer.get(GameTimer::RoundTime) <= 3600 * 2) {
                         let mut packet = Packet::new(PacketType::SERVER_GAME_DEATHTIMER_END);
                         
-                        if demon_count < peer_count / 2 {
+                        if self.can_demonize(peer_count, demon_count) {
                             peer.lock().unwrap().player.as_mut().unwrap().revival_times = 2;
                             packet.wu8(1);
                             info!("{} (ID {}) was demonized!", peer.lock().unwrap().nickname, id);
@@ -348,7 +356,7 @@ impl State for Game
                     timer: 5 * 60
                 }));
                 
-                self.timer.set(GameTimer::TailsProjectile, 10 * 60);
+                self.timer.set(GameTimer::TailsProjectile, self.rules.ability_cooldown * 60);
             },
 
             // Destroy projectile
@@ -372,7 +380,7 @@ impl State for Game
                     activated_by: 0
                 }));
 
-                self.timer.set(GameTimer::TailsProjectile, 10 * 60);
+                self.timer.set(GameTimer::EggmanTracker, self.rules.ability_cooldown * 60);
             },
 
             PacketType::CLIENT_ETRACKER_ACTIVATED => {
@@ -437,7 +445,7 @@ impl State for Game
                     }
                 }
 
-                self.timer.set(GameTimer::CreamRing, 10 * 60);
+                self.timer.set(GameTimer::CreamRing, self.rules.ability_cooldown * 60);
             },
 
             PacketType::CLIENT_RING_COLLECTED => {
@@ -499,7 +507,7 @@ impl State for Game
                 packet.wu16(y);
                 server.multicast_real(&mut packet);
 
-                self.timer.set(GameTimer::ExetiorRing, 10 * 60);
+                self.timer.set(GameTimer::ExetiorRing, self.rules.ability_cooldown * 60);
             },
 
             PacketType::CLIENT_BRING_COLLECTED => {
@@ -557,6 +565,19 @@ impl State for Game
                 }
             },
 
+            // Queued peers may ask to be moved to a free lobby
+            PacketType::CLIENT_CHAT_MESSAGE => {
+                let _id = packet.ru16()?; //TODO: get rid of
+                let msg = packet.rstr()?;
+
+                if peer.lock().unwrap().in_queue && msg == ".move" {
+                    balancer::request_move(server, peer.clone());
+                }
+                else if passtrough {
+                    server.multicast_real_except(packet, id);
+                }
+            },
+
             _ => {                
                 if passtrough {
                     server.multicast_real_except(packet, id);
@@ -634,7 +655,15 @@ impl State for Game
             if self.recp.len() >= real_peers!(server).count() {
                 self.started = true;
 
-                self.map.clone().lock().unwrap().init(server, self);
+                {
+                    let map = self.map.clone();
+                    let mut map = map.lock().unwrap();
+                    map.init(server, self);
+
+                    for peer in self.recp.keys().filter_map(|x| server.peers.read().unwrap().get(x).cloned()).collect::<Vec<_>>() {
+                        map.peer_ready(server, self, peer);
+                    }
+                }
 
                 let mut packet = Packet::new(PacketType::SERVER_GAME_PLAYERS_READY);
                 server.multicast_real(&mut packet);
@@ -649,6 +678,7 @@ impl State for Game
         Ok(())
     }
 
+    fn map(&self) -> Option<Arc<Mutex<dyn Map>>> { Some(self.map.clone()) }
     fn name(&self) -> &str { "Game" }
 }
 
@@ -674,7 +704,8 @@ impl Game
             entity_id: 0,
             entity_destroy_queue: Vec::new(),
 
-            players_pos: HashMap::new()
+            players_pos: HashMap::new(),
+            rules: Rules::default()
         }
     }
 
@@ -716,6 +747,15 @@ impl Game
         debug!("Queued destruction of entity (ID {})", *id);
     }
 
+    // Positions stay at (0, 0) until the player sends any data, so those are never near
+    pub fn player_near(&self, id: u16, x: f32, y: f32, range: f32) -> bool
+    {
+        match self.players_pos.get(&id) {
+            Some(pos) if *pos != (0.0, 0.0) => ((pos.0 - x).powi(2) + (pos.1 - y).powi(2)).sqrt() <= range,
+            _ => false
+        }
+    }
+
     fn entity_check_destroy(&mut self, server: &mut Server)
     {
         if self.entity_destroy_queue.len() <= 0 {
@@ -744,10 +784,69 @@ impl Game
         self.entity_destroy_queue.clear();
     }
 
+    fn can_demonize(&self, peer_count: usize, demon_count: usize) -> bool
+    {
+        (demon_count as f32) < (peer_count as f32 * self.rules.demonize_ratio).floor()
+    }
+
+    // Maps can open the big ring before it's time
+    pub fn activate_big_ring(&mut self, server: &mut Server)
+    {
+        if !self.big_ring_spawn || self.big_ring_ready {
+            return;
+        }
+
+        // Escapes are checked against this
+        let game_time = self.timer.get(GameTimer::RoundTime);
+        if game_time > self.big_ring_time {
+            self.big_ring_time = game_time;
+        }
+
+        self.big_ring_ready = true;
+
+        let mut packet = Packet::new(PacketType::SERVER_GAME_SPAWN_RING);
+        packet.wu8(true as u8);
+        packet.wu8(thread_rng().gen_range(0..255));
+        server.multicast_real(&mut packet);
+
+        info!("Big ring activate!");
+    }
+
+    // Fills some of the free ring slots, more players means more rings at once
+    fn spawn_rings(&mut self, server: &mut Server)
+    {
+        let multiplier;
+        {
+            let map = self.map.lock().unwrap();
+            multiplier = map.player_time_multiplier(server);
+            self.ring_time = map.ring_time(server) as u16;
+        }
+
+        self.timer.set(GameTimer::RingSpawn, self.ring_time);
+
+        let mut free: Vec<usize> = (0..self.rings.len()).filter(|x| !self.rings[*x]).collect();
+        if free.is_empty() {
+            return;
+        }
+
+        free.shuffle(&mut thread_rng());
+        let count = ((self.rings.len() as f32 * multiplier * 0.25).ceil() as usize).clamp(1, free.len());
+
+        for slot in free.into_iter().take(count) {
+            self.rings[slot] = true;
+            self.spawn(server, Box::new(Ring { red: false, id: slot }));
+        }
+    }
+
     fn do_timers(&mut self, server: &mut Server)
     {
         let game_time = self.timer.get(GameTimer::RoundTime);
 
+        // Ring spawner, first rings appear as soon as the round starts
+        if self.timer.get(GameTimer::RingSpawn) == 0 {
+            self.spawn_rings(server);
+        }
+
         // Player death timer
         let mut packets = Vec::new();
         for peer in real_peers!(server)
@@ -780,7 +879,7 @@ impl Game
                 if death_timer == 0 {
                     let mut packet = Packet::new(PacketType::SERVER_GAME_DEATHTIMER_END);
                     
-                    if demon_count < peer_count / 2 {
+                    if self.can_demonize(peer_count, demon_count) {
                         peer.lock().unwrap().player.as_mut().unwrap().revival_times = 2;
                         packet.wu8(1);
                         info!("{} (ID {}) was demonized!", peer.lock().unwrap().nickname, id);
@@ -808,7 +907,7 @@ impl Game
             server.multicast_real(packet);
         }
 
-        if self.big_ring_spawn {
+        if self.big_ring_spawn && !self.big_ring_ready {
             // Spawn big ring
             if game_time == 60 * 60 {
                 let mut packet = Packet::new(PacketType::SERVER_GAME_SPAWN_RING);
@@ -821,14 +920,7 @@ impl Game
 
             // Activate big ring
             if game_time == self.big_ring_time {
-                self.big_ring_ready = true;
-
-                let mut packet = Packet::new(PacketType::SERVER_GAME_SPAWN_RING);
-                packet.wu8(true as u8);
-                packet.wu8(thread_rng().gen_range(0..255));
-                server.multicast_real(&mut packet);
-
-                info!("Big ring activate!");
+                self.activate_big_ring(server);
             }
         }
 
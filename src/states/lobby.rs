@@ -1,7 +1,7 @@
 use std::sync::{Mutex, Arc};
 use log::{debug, info};
 use rand::{thread_rng, Rng};
-use crate::{state::State, server::{Server, Peer, real_peers, assert_or_disconnect}, packet::{Packet, PacketType, self}};
+use crate::{balancer, config, state::State, server::{Server, Peer, real_peers, assert_or_disconnect}, packet::{Packet, PacketType, self}};
 
 use super::mapvote::MapVote;
 
@@ -18,6 +18,7 @@ impl State for Lobby
 {
     fn init(&mut self, server: &mut Server) -> Option<Box<dyn State>>
     {
+        let rules = config::get().rules(&server.name, None);
         for peer in server.peers.write().unwrap().values_mut() {
             let mut peer = peer.lock().unwrap();
             peer.ready = false;
@@ -36,13 +37,13 @@ impl State for Lobby
                 peer.send(&mut packet);
             }
 
-            peer.exe_chance += thread_rng().gen_range(2..10);
-            if peer.exe_chance > 99 {
-                peer.exe_chance = 99;
+            peer.exe_chance = peer.exe_chance.saturating_add(thread_rng().gen_range(rules.exe_chance_gain.0..rules.exe_chance_gain.1));
+            if peer.exe_chance > rules.exe_chance_max {
+                peer.exe_chance = rules.exe_chance_max;
             }
 
             if peer.player.is_some() && peer.player.as_ref().unwrap().exe {
-                peer.exe_chance = thread_rng().gen_range(0..2);
+                peer.exe_chance = thread_rng().gen_range(rules.exe_chance_reset.0..rules.exe_chance_reset.1);
             }
 
             // Send new exe chance
@@ -87,7 +88,7 @@ impl State for Lobby
 
             // Set state to map vote
             if self.countdown_timer <= 0 {
-                return Some(Box::new(MapVote::new()));
+                return Some(Box::new(MapVote::new(server)));
             }
 
             if self.countdown_timer % 60 == 0 {
@@ -132,29 +133,8 @@ impl State for Lobby
                     assert_or_disconnect!(!passtrough, peer);
                 }
                 
-                for plr in server.peers.read().unwrap().iter() {
-                    let plr = plr.1.lock().unwrap();
-
-                    if plr.pending {
-                        continue;
-                    }
-                    
-                    if plr.id() == id {
-                        continue;
-                    }
-
-                    let mut packet = Packet::new(PacketType::SERVER_LOBBY_PLAYER);
-                    packet.wu16(plr.id());
-                    packet.wu8(plr.ready as u8);
-                    packet.wstr(&plr.nickname);
-                    packet.wu8(plr.lobby_icon);
-                    packet.wi8(plr.pet);
-                    peer.lock().unwrap().send(&mut packet);
-                }
-
-                let mut packet = Packet::new(PacketType::SERVER_LOBBY_CORRECT);
-                peer.lock().unwrap().send(&mut packet);
-                self.send_message(&mut peer.lock().unwrap(), "type .help for more info");
+                self.send_player_list(server, peer.clone());
+                self.send_message(&mut peer.lock().unwrap(), &config::get().motd);
             },
 
             // Peer's ready state changed
@@ -183,13 +163,15 @@ impl State for Lobby
                     assert_or_disconnect!(!peer.pending, peer);
                 }
 
-                // Remulitcast the message
-                server.multicast_real_except(packet, id);
-
                 let _id = packet.ru16()?; //TODO: get rid of
                 let msg = packet.rstr()?;
 
                 info!("[{}]: {}", peer.lock().unwrap().nickname, msg);
+
+                // Remulitcast the message
+                if !self.handle_command(server, peer.clone(), &msg) {
+                    server.multicast_real_except(packet, id);
+                }
             },
 
             _ => {
@@ -207,8 +189,9 @@ impl State for Lobby
 
     fn connect(&mut self, server: &mut Server, peer: Arc<Mutex<Peer>>) -> Option<Box<dyn State>>
     {
-        if server.peers.read().unwrap().len() > 7 {
-            peer.lock().unwrap().disconnect("Server is full: 7/7.");
+        let max_players = config::get().rules(&server.name, None).max_players as usize;
+        if server.peers.read().unwrap().len() > max_players {
+            peer.lock().unwrap().disconnect(&format!("Server is full: {}/{}.", max_players, max_players));
             return None;
         }
 
@@ -236,6 +219,14 @@ impl State for Lobby
         None
     }
 
+    fn transferred(&mut self, server: &mut Server, peer: Arc<Mutex<Peer>>) -> Option<Box<dyn State>>
+    {
+        self.accept_player(&mut peer.lock().unwrap());
+        self.share_player(server, &mut peer.lock().unwrap());
+        self.send_player_list(server, peer);
+        self.check_ready(server);
+        None
+    }
 
     fn name(&self) -> &str {
         "Lobby"
@@ -260,6 +251,32 @@ impl Lobby
         server.multicast_except(&mut packet, peer.id());
     }
 
+    fn send_player_list(&mut self, server: &mut Server, peer: Arc<Mutex<Peer>>)
+    {
+        let id = peer.lock().unwrap().id();
+        for plr in server.peers.read().unwrap().iter() {
+            if *plr.0 == id {
+                continue;
+            }
+
+            let plr = plr.1.lock().unwrap();
+            if plr.pending {
+                continue;
+            }
+
+            let mut packet = Packet::new(PacketType::SERVER_LOBBY_PLAYER);
+            packet.wu16(plr.id());
+            packet.wu8(plr.ready as u8);
+            packet.wstr(&plr.nickname);
+            packet.wu8(plr.lobby_icon);
+            packet.wi8(plr.pet);
+            peer.lock().unwrap().send(&mut packet);
+        }
+
+        let mut packet = Packet::new(PacketType::SERVER_LOBBY_CORRECT);
+        peer.lock().unwrap().send(&mut packet);
+    }
+
     fn accept_player(&mut self, peer: &mut Peer) 
     {
         let mut packet = Packet::new(PacketType::SERVER_LOBBY_EXE_CHANCE);
@@ -269,6 +286,54 @@ impl Lobby
         peer.pending = false;
     }
 
+    // Returns true if message shouldn't be shown to others
+    fn handle_command(&mut self, server: &mut Server, peer: Arc<Mutex<Peer>>, message: &str) -> bool
+    {
+        let args: Vec<&str> = message.split_whitespace().collect();
+        if args.is_empty() {
+            return false;
+        }
+
+        match args[0] {
+            ".help" => {
+                let peer = &mut peer.lock().unwrap();
+                self.send_message(peer, ".room - create a private room");
+                self.send_message(peer, ".join <code> - join a private room");
+            },
+
+            ".room" => {
+                match balancer::create_room() {
+                    Some(code) => {
+                        self.send_message(&mut peer.lock().unwrap(), format!("room created, code: {}", code).as_str());
+                        balancer::request_join(server, peer, &code);
+                    },
+
+                    None => {
+                        self.send_message(&mut peer.lock().unwrap(), "couldn't create a room right now");
+                    }
+                }
+            },
+
+            ".join" => {
+                if args.len() < 2 {
+                    self.send_message(&mut peer.lock().unwrap(), "usage: .join <code>");
+                    return true;
+                }
+
+                balancer::request_join(server, peer, args[1]);
+            },
+
+            // Typing just the code works too
+            code if args.len() == 1 && balancer::room_exists(code) => {
+                balancer::request_join(server, peer, code);
+            },
+
+            _ => return false
+        }
+
+        true
+    }
+
     fn send_message(&mut self, peer: &mut Peer, message: &str)
     {
         let mut packet = Packet::new(PacketType::CLIENT_CHAT_MESSAGE);
@@ -311,7 +376,7 @@ impl Lobby
 
         if ready_count == count {
             self.countdown = true;
-            self.countdown_timer = 60 * 5;
+            self.countdown_timer = config::get().rules(&server.name, None).lobby_countdown * 60;
 
             let mut packet = Packet::new(PacketType::SERVER_LOBBY_COUNTDOWN);
             packet.wu8(self.countdown as u8);
@@ -4,11 +4,11 @@ use std::sync::{Mutex, Arc};
 
 use log::{debug, info};
 use rand::seq::SliceRandom;
-use rand::{thread_rng, Rng};
+use rand::thread_rng;
 
+use crate::config;
 use crate::map::Map;
-use crate::maps::hideandseek2::HideAndSeek2;
-use crate::maps::ravinemist::RavineMist;
+use crate::maps;
 use crate::packet::{Packet, PacketType};
 use crate::state::State;
 use crate::server::{Server, Peer, real_peers, assert_or_disconnect};
@@ -16,11 +16,12 @@ use crate::states::characterselect::CharacterSelect;
 
 use super::lobby::Lobby;
 
+const MAP_HISTORY: usize = u8::MAX as usize; // Enough for any maps.cooldown
+
 pub(crate) struct MapVote 
 {
     timer: u16,
     
-    map_list: Vec<Arc<Mutex<dyn Map>>>,
     vote_maps: Vec<Arc<Mutex<dyn Map>>>,
     voted_peers: Vec<u16>,
     votes: HashMap<u8, u8>
@@ -30,33 +31,22 @@ impl State for MapVote
 {
     fn init(&mut self, server: &mut Server) -> Option<Box<dyn State>> 
     {
+        self.vote_maps = self.choose_maps(server);
+
         // we should have at least one map
-        //TODO: finish
-        if self.map_list.len() <= 0 {
+        if self.vote_maps.is_empty() {
             return Some(Box::new(Lobby::new()));
         }
         
         for i in 0..3 {
             self.votes.insert(i, 0);
         }
-        
-
-        if self.map_list.len() >= 3 {
-            for _i in 0..3 {
-                let map = self.map_list[thread_rng().gen_range(0..self.map_list.len())].clone();
-                self.vote_maps.push(map);
-            }
-        }
-        else {
-            let mut last: Arc<Mutex<dyn Map>> = self.map_list[0].clone(); 
-            for map in &self.map_list {
-                self.vote_maps.push(map.clone());
-                last = map.clone();
-            }
 
-            for _i in 0..(3-self.vote_maps.len()) {
-                self.vote_maps.push(last.clone());
-            }
+        // Not enough maps in the pool, so some have to repeat
+        let mut i = 0;
+        while self.vote_maps.len() < 3 {
+            self.vote_maps.push(self.vote_maps[i].clone());
+            i += 1;
         }
 
         let mut packet = Packet::new(PacketType::SERVER_VOTE_MAPS);
@@ -74,6 +64,11 @@ impl State for MapVote
             let winner = self.vote_winner();
             info!("[MapVote] Map [{}] won!", winner.lock().unwrap().name());
 
+            server.map_history.push(winner.lock().unwrap().index());
+            if server.map_history.len() > MAP_HISTORY {
+                server.map_history.remove(0);
+            }
+
             return Some(Box::new(CharacterSelect::new(winner)));
         }
 
@@ -173,22 +168,58 @@ impl State for MapVote
 
 impl MapVote
 {
-    pub fn new() -> MapVote 
+    pub fn new(server: &Server) -> MapVote 
     {
         MapVote 
         {  
-            timer: 20 * 60,
-            map_list: Vec::from([
-                Arc::new(Mutex::new(HideAndSeek2::new())) as Arc<Mutex<dyn Map>>,
-                Arc::new(Mutex::new(RavineMist::new())),
-            ]),
-
+            timer: config::get().rules(&server.name, None).vote_time * 60,
             vote_maps: Vec::new(),
             voted_peers: Vec::new(),
             votes: HashMap::new(),
         }
     }
 
+    // Up to 3 different maps from the pool, recently played ones are only used if there's nothing else
+    fn choose_maps(&mut self, server: &mut Server) -> Vec<Arc<Mutex<dyn Map>>>
+    {
+        let pool = config::get().maps(&server.name);
+        let recent: Vec<usize> = server.map_history.iter().rev().take(pool.cooldown as usize).copied().collect();
+
+        let mut fresh = Vec::new();
+        let mut played = Vec::new();
+        for map in maps::all() {
+            let (weight, index) = {
+                let map = map.lock().unwrap();
+                (pool.weight(map.name(), map.index()), map.index())
+            };
+
+            if weight == 0 {
+                continue;
+            }
+
+            if recent.contains(&index) {
+                played.push((map, weight));
+            }
+            else {
+                fresh.push((map, weight));
+            }
+        }
+
+        let mut result: Vec<Arc<Mutex<dyn Map>>> = match fresh.choose_multiple_weighted(&mut thread_rng(), 3, |x| x.1 as f64) {
+            Ok(res) => res.map(|x| x.0.clone()).collect(),
+            Err(_) => Vec::new()
+        };
+
+        if result.len() < 3 {
+            if let Ok(res) = played.choose_multiple_weighted(&mut thread_rng(), 3 - result.len(), |x| x.1 as f64) {
+                result.extend(res.map(|x| x.0.clone()));
+            }
+        }
+
+        result.shuffle(&mut thread_rng());
+        result
+    }
+
     fn check_votes(&mut self, server: &mut Server) 
     {
         if self.voted_peers.len() < real_peers!(server).count() {